@@ -125,7 +125,7 @@ fn get_options(args: Vec<String>) -> Result<Vec<CommandOption>, Error> {
                 .to_string()
                 .replace("--", "")
                 .replace("=", "");
-            let value = options_regex.replace_all(&option.clone(), "").to_string();
+            let value = options_regex.replace(&option.clone(), "").to_string();
             CommandOption { name, value }
         });
 
@@ -197,3 +197,22 @@ fn it_should_return_a_valid_command_struct() {
 
     assert_eq!(correct_struct, struct_tested)
 }
+
+#[test]
+fn it_should_keep_equal_signs_in_option_values() {
+    let command: Vec<String> = vec![
+        "tp".to_string(),
+        "save".to_string(),
+        "--replace=my-app=name".to_string(),
+    ];
+
+    let options = parse_command(command).unwrap().options;
+
+    assert_eq!(
+        options,
+        vec![CommandOption {
+            name: "replace".to_string(),
+            value: "my-app=name".to_string(),
+        }]
+    )
+}
@@ -3,7 +3,7 @@ pub mod namespaces;
 
 use self::command::Command;
 use crate::utils::errors::invalid_input_error;
-use std::io::{stdin, stdout, Error, Write};
+use std::io::{stderr, stdin, stdout, Error, Write};
 
 pub fn check_flags(flags: &Vec<String>, expected_flags: Vec<&str>) -> Result<(), Error> {
     let invalid_flag = flags.into_iter().find(|flag| {
@@ -32,6 +32,32 @@ pub fn get(text: &str) -> Result<String, Error> {
     Ok(info.trim().to_string())
 }
 
+// Same as get, but the text goes to stderr when stdout is kept for the command
+// output, like a JSON report.
+pub fn prompt(text: &str, to_stderr: bool) -> Result<String, Error> {
+    if !to_stderr {
+        return get(text);
+    }
+
+    eprint!("{}", text);
+    stderr().flush()?;
+
+    let mut info = String::new();
+    stdin().read_line(&mut info)?;
+
+    Ok(info.trim().to_string())
+}
+
+pub fn prompt_boolean(text: &str, to_stderr: bool) -> Result<bool, Error> {
+    loop {
+        match prompt(text, to_stderr)?.as_str() {
+            "y" | "Y" => return Ok(true),
+            "n" | "N" => return Ok(false),
+            _ => continue,
+        }
+    }
+}
+
 pub fn get_valid_input<F: Fn(&str) -> bool>(
     text: &str,
     invalid_input_msg: Option<&str>,
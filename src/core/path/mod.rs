@@ -7,6 +7,26 @@ pub fn get_remote_repo_reg_file_path() -> Result<PathBuf, Error> {
     Ok(get_app_local_path()?.join("remote-repos-registry.json"))
 }
 
+pub fn get_trusted_templates_file_path() -> Result<PathBuf, Error> {
+    Ok(get_app_local_path()?.join("trusted-templates.json"))
+}
+
+pub fn get_template_snapshots_path() -> Result<PathBuf, Error> {
+    Ok(get_app_local_path()?.join("Snapshots"))
+}
+
+pub fn get_generations_path() -> Result<PathBuf, Error> {
+    Ok(get_app_local_path()?.join("Generations"))
+}
+
+// Ignore patterns applied to every template saved by the user
+pub fn get_global_ignore_file_path() -> Result<PathBuf, Error> {
+    match home::home_dir() {
+        Some(path) => Ok(path.join(".temploignore")),
+        None => Err(not_found_error("Not is possible to get your home folder.")),
+    }
+}
+
 pub fn get_root_repos_path() -> Result<PathBuf, Error> {
     let data_path = get_app_local_path()?;
     Ok(data_path.join("Repositories"))
@@ -120,12 +120,7 @@ impl Repository {
 
         let new_template = Template {
             name: new_template_name,
-            description: old_template.description,
-            contents: old_template.contents,
-            created_at: old_template.created_at,
-            updated_at: old_template.updated_at,
-            paths: old_template.paths,
-            args: old_template.args,
+            ..old_template
         };
 
         self.save_template(new_template)?;
@@ -142,13 +137,8 @@ impl Repository {
         self.delete_template(template_name)?;
 
         let new_template = Template {
-            name: old_template.name,
             description: new_template_description,
-            contents: old_template.contents,
-            created_at: old_template.created_at,
-            updated_at: old_template.updated_at,
-            paths: old_template.paths,
-            args: old_template.args,
+            ..old_template
         };
 
         self.save_template(new_template)?;
@@ -0,0 +1,310 @@
+use super::config::{parse_config_manifest, ConfigManifest, CONFIG_MANIFEST_FILENAMES};
+use super::maker::TempData;
+use super::miner::{build_ignore_matcher, TEMPLO_IGNORE_FILENAME};
+use super::sink::ArchiveFormat;
+use super::{TempContent, TempPath, TempPathType};
+use crate::core::path::get_global_ignore_file_path;
+use crate::utils::errors::{invalid_data_error, std_error};
+use crate::utils::path::pathbuf_to_string;
+use flate2::read::GzDecoder;
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    Match,
+};
+use serde::de::DeserializeOwned;
+use serde_json::from_str;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Error, Read},
+    path::{Path, PathBuf},
+};
+
+// Archive entries by path, folders have no bytes. The paths are ordered by
+// components, so the parent folders come before their children.
+pub type ArchiveFiles = BTreeMap<PathBuf, Option<Vec<u8>>>;
+
+pub fn is_archive_path(path: &str) -> bool {
+    Path::new(path).is_file() && ArchiveFormat::from_filename(path).is_ok()
+}
+
+// When all the entries are inside a single folder, like in the archives
+// downloaded from GitHub, that folder is the template root.
+pub fn read_archive(filename: &str) -> Result<ArchiveFiles, Error> {
+    let file = File::open(filename)?;
+    let mut files = match ArchiveFormat::from_filename(filename)? {
+        ArchiveFormat::TarGz => read_tar(GzDecoder::new(file))?,
+        ArchiveFormat::Zip => read_zip(file)?,
+    };
+    add_parent_folders(&mut files);
+
+    Ok(strip_root_folder(files))
+}
+
+// Entries of an uncompressed tar stream, like the "git archive" output.
+// The entries are already relative to the template root.
+pub fn read_tar_stream(bytes: &[u8]) -> Result<ArchiveFiles, Error> {
+    let mut files = read_tar(bytes)?;
+    add_parent_folders(&mut files);
+    Ok(files)
+}
+
+pub fn get_archive_config<T: DeserializeOwned>(
+    files: &ArchiveFiles,
+    filename: &str,
+) -> Result<Option<T>, Error> {
+    match files.get(&Path::new("TemplateConfig").join(filename)) {
+        Some(Some(bytes)) => Ok(Some(std_error(from_str(&String::from_utf8_lossy(bytes)))?)),
+        _ => Ok(None),
+    }
+}
+
+pub fn get_archive_manifest(files: &ArchiveFiles) -> Result<Option<ConfigManifest>, Error> {
+    for filename in CONFIG_MANIFEST_FILENAMES.iter() {
+        if let Some(Some(bytes)) = files.get(Path::new(filename)) {
+            let manifest = parse_config_manifest(filename, &String::from_utf8_lossy(bytes))?;
+            return Ok(Some(manifest));
+        }
+    }
+
+    Ok(None)
+}
+
+// Same rules as the folders mining: the TemplateConfig ignore patterns, the
+// .temploignore files and, when gitignore is true, the .gitignore files.
+pub fn mine_archive_data(files: &ArchiveFiles, gitignore: bool) -> Result<TempData, Error> {
+    let ignore_files = get_ignore_files(files, gitignore)?;
+    mine_archive_files(files, &ignore_files)
+}
+
+// The files tracked at a git commit are saved as they are, only the
+// TemplateConfig ignore patterns are applied.
+pub fn mine_git_archive_data(files: &ArchiveFiles) -> Result<TempData, Error> {
+    mine_archive_files(files, &[])
+}
+
+fn mine_archive_files(
+    files: &ArchiveFiles,
+    ignore_files: &[(PathBuf, Gitignore)],
+) -> Result<TempData, Error> {
+    let ignore_patterns: Vec<String> = match get_archive_manifest(files)?.and_then(|m| m.ignore) {
+        Some(patterns) => patterns,
+        None => get_archive_config(files, "ignore.json")?.unwrap_or_default(),
+    };
+    let config_matcher = build_ignore_matcher("", &ignore_patterns)?;
+
+    let is_ignored = |path: &Path, is_dir: bool| {
+        path.components()
+            .any(|component| component.as_os_str() == ".git")
+            || config_matcher.matched(path, is_dir).is_ignore()
+            || is_ignored_by_files(ignore_files, path, is_dir)
+    };
+
+    let mut paths = vec![];
+    let mut contents = vec![];
+
+    for (path, bytes) in files.iter() {
+        let ignored = path
+            .ancestors()
+            .skip(1)
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .any(|parent| is_ignored(parent, true))
+            || is_ignored(path, bytes.is_none());
+
+        if ignored {
+            continue;
+        }
+
+        let path_type = match bytes {
+            Some(bytes) => {
+                if !bytes.is_empty() {
+                    contents.push(TempContent::new(
+                        pathbuf_to_string(path.clone()),
+                        base64::encode(bytes),
+                    ));
+                }
+                TempPathType::File
+            }
+            None => TempPathType::Dir,
+        };
+
+        paths.push(TempPath {
+            path: path.clone(),
+            path_type,
+        });
+    }
+
+    Ok(TempData { paths, contents })
+}
+
+fn read_tar<R: Read>(reader: R) -> Result<ArchiveFiles, Error> {
+    let mut archive = tar::Archive::new(reader);
+    let mut files = ArchiveFiles::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_type = entry.header().entry_type();
+
+        // Symbolic links are not saved in the template
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            continue;
+        }
+
+        let path = match get_entry_path(&pathbuf_to_string(entry.path()?.to_path_buf()))? {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let bytes = if entry_type.is_dir() {
+            None
+        } else {
+            let mut bytes = vec![];
+            entry.read_to_end(&mut bytes)?;
+            Some(bytes)
+        };
+        files.insert(path, bytes);
+    }
+
+    Ok(files)
+}
+
+fn read_zip(file: File) -> Result<ArchiveFiles, Error> {
+    let mut archive = std_error(zip::ZipArchive::new(file))?;
+    let mut files = ArchiveFiles::new();
+
+    for idx in 0..archive.len() {
+        let mut entry = std_error(archive.by_index(idx))?;
+
+        let path = match get_entry_path(entry.name())? {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let bytes = if entry.is_dir() {
+            None
+        } else {
+            let mut bytes = vec![];
+            entry.read_to_end(&mut bytes)?;
+            Some(bytes)
+        };
+        files.insert(path, bytes);
+    }
+
+    Ok(files)
+}
+
+// The entries are relative to the archive root, entries that would be generated
+// outside of the output folder are rejected.
+fn get_entry_path(name: &str) -> Result<Option<PathBuf>, Error> {
+    let name = name.replace('\\', "/");
+    let components: Vec<&str> = name
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+
+    if name.starts_with('/')
+        || components
+            .iter()
+            .any(|component| *component == ".." || component.contains(':'))
+    {
+        return Err(invalid_data_error(&format!(
+            "The archive entry \"{}\" is outside of the archive root.",
+            name
+        )));
+    }
+
+    if components.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(PathBuf::from(components.join("/"))))
+}
+
+// Some archives only have entries for the files
+fn add_parent_folders(files: &mut ArchiveFiles) {
+    let parent_folders: Vec<PathBuf> = files
+        .keys()
+        .flat_map(|path| {
+            path.ancestors()
+                .skip(1)
+                .map(Path::to_path_buf)
+                .collect::<Vec<_>>()
+        })
+        .filter(|path| !path.as_os_str().is_empty())
+        .collect();
+    for folder in parent_folders {
+        files.entry(folder).or_insert(None);
+    }
+}
+
+fn strip_root_folder(files: ArchiveFiles) -> ArchiveFiles {
+    let mut roots = files.keys().filter(|path| path.components().count() == 1);
+
+    let root = match (roots.next(), roots.next()) {
+        (Some(root), None) if files[root].is_none() => root.clone(),
+        _ => return files,
+    };
+
+    files
+        .into_iter()
+        .filter(|(path, _)| *path != root)
+        .map(|(path, bytes)| (path.strip_prefix(&root).unwrap().to_path_buf(), bytes))
+        .collect()
+}
+
+// The ignore files of the archive, with the folder they apply to, from the less
+// to the more specific. The global .temploignore applies to the whole archive.
+fn get_ignore_files(
+    files: &ArchiveFiles,
+    gitignore: bool,
+) -> Result<Vec<(PathBuf, Gitignore)>, Error> {
+    let mut ignore_files = vec![];
+
+    let global_ignore_file = get_global_ignore_file_path()?;
+    if global_ignore_file.is_file() {
+        let mut builder = GitignoreBuilder::new("");
+        if let Some(err) = builder.add(global_ignore_file) {
+            return Err(invalid_data_error(&err.to_string()));
+        }
+        ignore_files.push((PathBuf::new(), std_error(builder.build())?));
+    }
+
+    let mut archive_ignore_files: Vec<(&PathBuf, &Vec<u8>)> = files
+        .iter()
+        .filter_map(|(path, bytes)| Some((path, bytes.as_ref()?)))
+        .filter(|(path, _)| {
+            let filename = path.file_name().unwrap_or_default();
+            filename == TEMPLO_IGNORE_FILENAME || (gitignore && filename == ".gitignore")
+        })
+        .collect();
+    archive_ignore_files.sort_by_key(|(path, _)| path.components().count());
+
+    for (path, bytes) in archive_ignore_files {
+        let folder = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        let mut builder = GitignoreBuilder::new(&folder);
+
+        for line in String::from_utf8_lossy(bytes).lines() {
+            std_error(builder.add_line(None, line))?;
+        }
+        ignore_files.push((folder, std_error(builder.build())?));
+    }
+
+    Ok(ignore_files)
+}
+
+// The more specific ignore file that matches the path decides, like git does
+fn is_ignored_by_files(ignore_files: &[(PathBuf, Gitignore)], path: &Path, is_dir: bool) -> bool {
+    for (folder, matcher) in ignore_files.iter().rev() {
+        if !path.starts_with(folder) {
+            continue;
+        }
+
+        match matcher.matched(path, is_dir) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => continue,
+        }
+    }
+
+    false
+}
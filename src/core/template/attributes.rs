@@ -0,0 +1,124 @@
+use super::config::{ConfigAttribute, LineEnding};
+use crate::utils::errors::{invalid_data_error, invalid_input_error, std_error};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use globset::GlobBuilder;
+use std::{io::Error, path::Path};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileAttributes {
+    pub eol: LineEnding,
+    pub encoding: &'static Encoding,
+    pub binary: bool,
+}
+
+impl Default for FileAttributes {
+    fn default() -> Self {
+        Self {
+            eol: LineEnding::Preserve,
+            encoding: UTF_8,
+            binary: false,
+        }
+    }
+}
+
+// Matches the attributes like .gitattributes: a pattern without a slash matches the
+// file name at any depth, otherwise it is relative to the template root. When
+// more than one pattern matches a file, the last one wins.
+pub fn get_file_attributes(
+    attributes: &[ConfigAttribute],
+    file_path: &str,
+) -> Result<FileAttributes, Error> {
+    let mut file_attributes = FileAttributes::default();
+
+    for attribute in attributes.iter() {
+        if !matches_pattern(&attribute.pattern, file_path)? {
+            continue;
+        }
+
+        if let Some(eol) = &attribute.eol {
+            file_attributes.eol = eol.clone();
+        }
+
+        if let Some(label) = &attribute.encoding {
+            file_attributes.encoding = match Encoding::for_label(label.as_bytes()) {
+                Some(encoding) => encoding,
+                None => {
+                    return Err(invalid_input_error(&format!(
+                        "Unknown encoding \"{}\" in template attributes.",
+                        label
+                    )))
+                }
+            };
+        }
+
+        if let Some(binary) = attribute.binary {
+            file_attributes.binary = binary;
+        }
+    }
+
+    Ok(file_attributes)
+}
+
+// Returns None for binary files and for files that are not valid in their encoding
+pub fn decode_text(bytes: &[u8], attributes: &FileAttributes) -> Option<String> {
+    if attributes.binary {
+        return None;
+    }
+
+    attributes
+        .encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(|text| text.into_owned())
+}
+
+// The encoder of encoding_rs writes UTF-8 for UTF-16 (like the web does), so
+// UTF-16 is encoded here. Characters that the encoding can not write are an error,
+// instead of the HTML numeric references written by the encoder.
+pub fn encode_text(
+    text: &str,
+    attributes: &FileAttributes,
+    file_path: &str,
+) -> Result<Vec<u8>, Error> {
+    let encoding = attributes.encoding;
+
+    if encoding == UTF_16LE {
+        return Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect());
+    }
+    if encoding == UTF_16BE {
+        return Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect());
+    }
+
+    let (bytes, _, unmappable) = encoding.encode(text);
+    if unmappable {
+        return Err(invalid_data_error(&format!(
+            "The file \"{}\" has characters that can not be encoded in {}.",
+            file_path,
+            encoding.name()
+        )));
+    }
+
+    Ok(bytes.into_owned())
+}
+
+pub fn convert_line_endings(text: &str, eol: &LineEnding) -> String {
+    match eol {
+        LineEnding::Preserve => text.to_string(),
+        LineEnding::Lf => text.replace("\r\n", "\n"),
+        LineEnding::Crlf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+    }
+}
+
+pub fn matches_pattern(pattern: &str, file_path: &str) -> Result<bool, Error> {
+    let (pattern, target) = if pattern.contains('/') {
+        (pattern.trim_start_matches('/'), file_path)
+    } else {
+        let file_name = Path::new(file_path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(file_path);
+        (pattern, file_name)
+    };
+
+    let glob = std_error(GlobBuilder::new(pattern).literal_separator(true).build())?;
+    Ok(glob.compile_matcher().is_match(target))
+}
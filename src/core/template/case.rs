@@ -0,0 +1,107 @@
+use crate::utils::errors::invalid_input_error;
+use std::io::Error;
+
+// Case applied to an arg value with the key modifier, like {> name|pascal <}
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TextCase {
+    Pascal,
+    Camel,
+    Snake,
+    Kebab,
+    UpperSnake,
+}
+
+impl TextCase {
+    pub const ALL: [TextCase; 5] = [
+        Self::Pascal,
+        Self::Camel,
+        Self::Snake,
+        Self::Kebab,
+        Self::UpperSnake,
+    ];
+
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match Self::ALL.iter().find(|case| case.name() == name) {
+            Some(case) => Ok(*case),
+            None => Err(invalid_input_error(&format!(
+                "Invalid case \"{}\", use pascal, camel, snake, kebab or upper_snake.",
+                name
+            ))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pascal => "pascal",
+            Self::Camel => "camel",
+            Self::Snake => "snake",
+            Self::Kebab => "kebab",
+            Self::UpperSnake => "upper_snake",
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let words = split_words(text);
+
+        match self {
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Self::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(idx, word)| {
+                    if idx == 0 {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Self::Snake => words.join("_"),
+            Self::Kebab => words.join("-"),
+            Self::UpperSnake => words.join("_").to_uppercase(),
+        }
+    }
+}
+
+// Splits the text in lowercase words by the separators (-, _ and whitespaces)
+// and by the case changes, so "myApp", "my-app" and "MY_APP" are all ["my", "app"].
+pub fn split_words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    let mut previous: Option<char> = None;
+
+    for ch in text.chars() {
+        if ch == '-' || ch == '_' || ch.is_whitespace() {
+            if !word.is_empty() {
+                words.push(word.clone());
+                word.clear();
+            }
+            previous = None;
+            continue;
+        }
+
+        let is_case_change = matches!(previous, Some(previous) if ch.is_uppercase() && (previous.is_lowercase() || previous.is_numeric()));
+        if is_case_change && !word.is_empty() {
+            words.push(word.clone());
+            word.clear();
+        }
+
+        word.extend(ch.to_lowercase());
+        previous = Some(ch);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,130 @@
+use super::config::ConfigArg;
+use super::{TempPathType, TempSource, Template};
+use crate::utils::errors::{already_exists_error, invalid_input_error};
+use crate::utils::path::get_path_name;
+use std::{io::Error, path::Path};
+
+// What to do when a file exists in more than one template layer
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConflictPolicy {
+    Override,
+    Keep,
+    Error,
+}
+
+impl ConflictPolicy {
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "override" => Ok(Self::Override),
+            "keep" => Ok(Self::Keep),
+            "error" => Ok(Self::Error),
+            _ => Err(invalid_input_error(&format!(
+                "Invalid conflict policy \"{}\", use override, keep or error.",
+                name
+            ))),
+        }
+    }
+}
+
+// Merges the template layers, in order, into a single template. Args are
+// deduplicated by key, keeping the first layer question.
+pub fn compose_templates(
+    templates: Vec<Template>,
+    policy: &ConflictPolicy,
+) -> Result<Template, Error> {
+    let mut layers = templates.into_iter();
+    let mut composed = match layers.next() {
+        Some(template) => template,
+        None => return Err(invalid_input_error("No templates to compose.")),
+    };
+
+    for layer in layers {
+        for path in layer.paths.into_iter() {
+            let path_name = get_path_name(&path.path);
+            let current = composed
+                .paths
+                .iter()
+                .find(|current| get_path_name(&current.path) == path_name);
+
+            match current {
+                None => composed.paths.push(path),
+                Some(current) => {
+                    if current.path_type == TempPathType::Dir && path.path_type == TempPathType::Dir
+                    {
+                        continue;
+                    }
+
+                    match policy {
+                        ConflictPolicy::Keep => continue,
+                        ConflictPolicy::Error => {
+                            return Err(already_exists_error(&format!(
+                                "The path \"{}\" exists in \"{}\" and \"{}\" templates.",
+                                path_name, composed.name, layer.name
+                            )))
+                        }
+                        ConflictPolicy::Override => {
+                            composed
+                                .paths
+                                .retain(|current| get_path_name(&current.path) != path_name);
+                            composed.contents.retain(|content| {
+                                get_path_name(Path::new(&content.file_path)) != path_name
+                            });
+                            composed.paths.push(path);
+                        }
+                    }
+                }
+            }
+        }
+
+        for content in layer.contents.into_iter() {
+            let content_name = get_path_name(Path::new(&content.file_path));
+            let exists = composed
+                .contents
+                .iter()
+                .any(|current| get_path_name(Path::new(&current.file_path)) == content_name);
+
+            if !exists {
+                composed.contents.push(content);
+            }
+        }
+
+        composed.args = merge_args(composed.args, layer.args);
+        composed.hooks = merge_options(composed.hooks, layer.hooks);
+        composed.requirements = merge_options(composed.requirements, layer.requirements);
+        composed.injections = merge_options(composed.injections, layer.injections);
+        // The last matching attribute wins, so the later layers take precedence
+        composed.attributes = merge_options(composed.attributes, layer.attributes);
+        composed.source = TempSource::least_trusted(composed.source, layer.source);
+        composed.name = format!("{}+{}", composed.name, layer.name);
+    }
+
+    Ok(composed)
+}
+
+fn merge_args(
+    args: Option<Vec<ConfigArg>>,
+    layer_args: Option<Vec<ConfigArg>>,
+) -> Option<Vec<ConfigArg>> {
+    let mut merged: Vec<ConfigArg> = vec![];
+
+    for arg in merge_options(args, layer_args)? {
+        if !merged.iter().any(|current| current.key == arg.key) {
+            merged.push(arg);
+        }
+    }
+
+    Some(merged)
+}
+
+fn merge_options<T>(items: Option<Vec<T>>, layer_items: Option<Vec<T>>) -> Option<Vec<T>> {
+    match (items, layer_items) {
+        (None, None) => None,
+        (items, layer_items) => Some(
+            items
+                .into_iter()
+                .flatten()
+                .chain(layer_items.into_iter().flatten())
+                .collect(),
+        ),
+    }
+}
@@ -1,10 +1,16 @@
-use crate::utils::errors::std_error;
+use crate::utils::errors::{invalid_data_error, invalid_input_error, std_error};
 use crate::utils::path::str_to_pathbuf;
+use serde::de::DeserializeOwned;
 use serde_derive::{Deserialize, Serialize};
-use serde_json::from_str;
+use serde_json::{from_str, from_value, Map, Value};
 use std::fs;
 use std::io::Error;
 use std::path::{Path, PathBuf};
+use yaml_rust::{Yaml, YamlLoader};
+
+// Single file alternative to the TemplateConfig folder, at the template root
+pub const CONFIG_MANIFEST_FILENAMES: [&str; 4] =
+    ["templo.toml", "templo.yaml", "templo.yml", "templo.json"];
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct ConfigArg {
@@ -12,18 +18,182 @@ pub struct ConfigArg {
     pub query: String,
     pub about: Option<String>,
     pub default: Option<String>,
+    pub secret: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ConfigRequirement {
+    pub name: String,
+    pub version: Option<String>,
+    pub version_arg: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ConfigInjection {
+    pub file: String,
+    pub snippet: String,
+    pub marker: String,
+    pub position: Option<InjectionPosition>,
+    pub regex: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum InjectionPosition {
+    Before,
+    After,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ConfigAttribute {
+    pub pattern: String,
+    pub eol: Option<LineEnding>,
+    pub encoding: Option<String>,
+    pub binary: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    Preserve,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ConfigExtends {
+    pub template: String,
+    pub delete: Option<Vec<String>>,
+}
+
+// A secret finding that is expected in the template, like the example key of a
+// test fixture. The finding is allowed when every given field matches it.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ConfigAllowedSecret {
+    pub file: Option<String>,
+    pub rule: Option<String>,
+    pub text: Option<String>,
+}
+
+// Every setting of the TemplateConfig folder, plus the template name and description.
+// The manifest settings take precedence over the TemplateConfig files.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigManifest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub args: Option<Vec<ConfigArg>>,
+    pub ignore: Option<Vec<String>>,
+    pub hooks: Option<Vec<String>>,
+    pub requirements: Option<Vec<ConfigRequirement>>,
+    pub injections: Option<Vec<ConfigInjection>>,
+    pub extends: Option<ConfigExtends>,
+    pub attributes: Option<Vec<ConfigAttribute>>,
+    pub secrets: Option<Vec<ConfigAllowedSecret>>,
+}
+
+pub fn get_config_manifest(directory_path: &str) -> Result<Option<ConfigManifest>, Error> {
+    let manifest_filenames: Vec<&str> = CONFIG_MANIFEST_FILENAMES
+        .iter()
+        .copied()
+        .filter(|filename| Path::new(directory_path).join(filename).is_file())
+        .collect();
+
+    match manifest_filenames[..] {
+        [] => Ok(None),
+        [filename] => {
+            let manifest_string = fs::read_to_string(Path::new(directory_path).join(filename))?;
+            Ok(Some(parse_config_manifest(filename, &manifest_string)?))
+        }
+        _ => Err(invalid_input_error(&format!(
+            "The template has more than one manifest: {}.",
+            manifest_filenames.join(", ")
+        ))),
+    }
+}
+
+// The format is given by the manifest file extension. A file with a manifest
+// name that is not a manifest, like the templo.json of another tool, is
+// reported with the file name.
+pub fn parse_config_manifest(filename: &str, text: &str) -> Result<ConfigManifest, Error> {
+    let manifest = if filename.ends_with(".toml") {
+        toml::from_str(text)
+            .map_err(|err| err.to_string())
+            .and_then(|toml| from_value(toml_to_json(toml)).map_err(|err| err.to_string()))
+    } else if filename.ends_with(".yaml") || filename.ends_with(".yml") {
+        let documents = std_error(YamlLoader::load_from_str(text))?;
+        match documents.into_iter().next() {
+            Some(document) => from_value(yaml_to_json(document)).map_err(|err| err.to_string()),
+            None => Ok(ConfigManifest::default()),
+        }
+    } else {
+        from_str(text).map_err(|err| err.to_string())
+    };
+
+    manifest.map_err(|err| {
+        invalid_data_error(&format!(
+            "\"{}\" is not a valid template manifest ({}). Rename the file if it is not a manifest.",
+            filename, err
+        ))
+    })
+}
+
+// Numbers are read as strings, like in the YAML manifests
+fn toml_to_json(toml: toml::Value) -> Value {
+    match toml {
+        toml::Value::String(string) => Value::String(string),
+        toml::Value::Integer(integer) => Value::String(integer.to_string()),
+        toml::Value::Float(float) => Value::String(float.to_string()),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Boolean(boolean) => Value::Bool(boolean),
+        toml::Value::Array(array) => Value::Array(array.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+// The manifest settings have no number fields, so numbers are read as strings,
+// like a "default: 8080" arg.
+fn yaml_to_json(yaml: Yaml) -> Value {
+    match yaml {
+        Yaml::String(string) | Yaml::Real(string) => Value::String(string),
+        Yaml::Integer(integer) => Value::String(integer.to_string()),
+        Yaml::Boolean(boolean) => Value::Bool(boolean),
+        Yaml::Array(array) => Value::Array(array.into_iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => {
+            let map: Map<String, Value> = hash
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match yaml_to_json(key) {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect();
+            Value::Object(map)
+        }
+        _ => Value::Null,
+    }
 }
 
-pub fn get_config_args(directory_path: &str) -> Result<Option<Vec<ConfigArg>>, Error> {
-    let args_filename = get_config_folder_path(directory_path).join("args.json");
+// Reads a json config file of the TemplateConfig folder, e.g. "args.json"
+pub fn get_config_file<T: DeserializeOwned>(
+    directory_path: &str,
+    filename: &str,
+) -> Result<Option<T>, Error> {
+    let config_filename = get_config_folder_path(directory_path).join(filename);
 
-    if !Path::new(&args_filename).exists() {
+    if !config_filename.exists() {
         return Ok(None);
     }
 
-    let args_string = fs::read_to_string(args_filename)?;
-    let config_args: Vec<ConfigArg> = std_error(from_str(&args_string))?;
-    Ok(Some(config_args))
+    let config_string = fs::read_to_string(config_filename)?;
+    Ok(Some(std_error(from_str(&config_string))?))
 }
 
 fn get_config_folder_path(directory_path: &str) -> PathBuf {
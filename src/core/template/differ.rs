@@ -0,0 +1,109 @@
+use super::engine::TempEngineArg;
+use super::generator::{get_rendered_files, render_template};
+use super::manifest::MANIFEST_FILENAME;
+use super::{miner, TempPathType, Template};
+use crate::utils::path::{format_path_namespace, pathbuf_to_string, remove_dir_prefix};
+use diffy::DiffOptions;
+use std::{fs, io::Error, path::Path, str};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DiffStatus {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileDiff {
+    pub path: String,
+    pub status: DiffStatus,
+    pub patch: String,
+}
+
+// Compares the template rendered in memory (a/) with the directory files (b/).
+// Files that only exist in the directory are reported as added.
+pub fn diff_template(
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &str,
+) -> Result<Vec<FileDiff>, Error> {
+    let render = render_template(template, temp_args)?;
+    let rendered_files = get_rendered_files(&render)?;
+    let mut diffs = vec![];
+
+    for path in render.paths.iter() {
+        if path.path_type != TempPathType::File {
+            continue;
+        }
+
+        let path_name = pathbuf_to_string(format_path_namespace(path.path.clone()));
+        let template_bytes = &rendered_files[&path_name];
+        let file_path = Path::new(directory).join(&path.path);
+
+        if !file_path.is_file() {
+            diffs.push(make_file_diff(
+                path_name,
+                DiffStatus::Removed,
+                template_bytes,
+                b"",
+            ));
+            continue;
+        }
+
+        let current_bytes = fs::read(&file_path)?;
+        if current_bytes != *template_bytes {
+            diffs.push(make_file_diff(
+                path_name,
+                DiffStatus::Changed,
+                template_bytes,
+                &current_bytes,
+            ));
+        }
+    }
+
+    // Nothing was generated in the directory yet
+    if !Path::new(directory).is_dir() {
+        return Ok(diffs);
+    }
+
+    for path in miner::mine_paths_from(directory, true)? {
+        if path.path_type != TempPathType::File {
+            continue;
+        }
+
+        let relative_path = remove_dir_prefix(path.clone(), directory)?;
+        let path_name = pathbuf_to_string(format_path_namespace(relative_path.path));
+
+        if path_name == MANIFEST_FILENAME || rendered_files.contains_key(&path_name) {
+            continue;
+        }
+
+        let current_bytes = fs::read(&path.path)?;
+        diffs.push(make_file_diff(
+            path_name,
+            DiffStatus::Added,
+            b"",
+            &current_bytes,
+        ));
+    }
+
+    Ok(diffs)
+}
+
+// Binary and non UTF-8 files get a one line patch, like git does
+fn make_file_diff(path: String, status: DiffStatus, original: &[u8], modified: &[u8]) -> FileDiff {
+    let patch = match (str::from_utf8(original), str::from_utf8(modified)) {
+        (Ok(original), Ok(modified)) => DiffOptions::new()
+            .set_original_filename(format!("a/{}", path))
+            .set_modified_filename(format!("b/{}", path))
+            .create_patch(original, modified)
+            .to_string(),
+        _ => format!("Binary files a/{} and b/{} differ\n", path, path),
+    };
+
+    FileDiff {
+        path,
+        status,
+        patch,
+    }
+}
@@ -1,10 +1,14 @@
-use super::ConfigArg;
+use super::attributes::get_file_attributes;
+use super::case::TextCase;
+use super::{ConfigArg, Template};
 use crate::cli::input;
-use crate::utils::errors::not_found_error;
+use crate::utils::errors::{not_found_error, std_error};
+use crate::utils::path::pathbuf_to_string;
 use regex::Regex;
+use serde_derive::{Deserialize, Serialize};
 use std::io::Error;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct TempEngineArg {
     pub key: String,
     pub value: String,
@@ -15,28 +19,118 @@ struct EngineRegex {
     pub key: Regex,
 }
 
-// Args shape: {> arg <}
+// Args shape: {> arg <} or {> arg|case <}
 pub fn parse_content(content: String, args: Vec<TempEngineArg>) -> Result<String, Error> {
-    parse(
-        content,
-        args,
-        EngineRegex {
-            shape: Regex::new(r"\{>[\w|\s|-]*<}").unwrap(),
-            key: Regex::new(r"[{}><\s]").unwrap(),
-        },
-    )
+    parse(content, args, get_content_regex())
 }
 
-// Args shape: ([ arg ])
+// Args shape: ([ arg ]) or ([ arg|case ])
 pub fn parse_path(path: String, args: Vec<TempEngineArg>) -> Result<String, Error> {
-    parse(
-        path,
-        args,
-        EngineRegex {
-            shape: Regex::new(r"\(\[[\w|\s|-]*\]\)").unwrap(),
-            key: Regex::new(r"[\(\)\[\]\s]").unwrap(),
-        },
-    )
+    parse(path, args, get_path_regex())
+}
+
+pub fn get_content_keys(content: &str) -> Vec<String> {
+    get_keys(content, get_content_regex())
+}
+
+pub fn get_path_keys(path: &str) -> Vec<String> {
+    get_keys(path, get_path_regex())
+}
+
+// A key used by the template and where it was found, e.g. the file "main.rs"
+pub type KeyUsage = (String, String);
+
+pub fn get_path_key_usages(template: &Template) -> Vec<KeyUsage> {
+    let mut usages = vec![];
+
+    for path in template.paths.iter() {
+        let path_name = pathbuf_to_string(path.path.clone());
+
+        for key in get_path_keys(&path_name) {
+            usages.push((key, format!("\"{}\"", path_name)));
+        }
+    }
+
+    for injection in template.injections.iter().flatten() {
+        for key in get_path_keys(&injection.file) {
+            usages.push((key, format!("the injection into \"{}\"", injection.file)));
+        }
+    }
+
+    usages
+}
+
+pub fn get_content_key_usages(template: &Template) -> Result<Vec<KeyUsage>, Error> {
+    let attributes = template.attributes.clone().unwrap_or_default();
+    let mut usages = vec![];
+
+    for content in template.contents.iter() {
+        // Binary files are written as they are, without parsing
+        if get_file_attributes(&attributes, &content.file_path)?.binary {
+            continue;
+        }
+
+        let bytes = std_error(base64::decode(&content.text))?;
+        for key in get_content_keys(&String::from_utf8_lossy(&bytes)) {
+            usages.push((key, format!("\"{}\"", content.file_path)));
+        }
+    }
+
+    for hook in template.hooks.iter().flatten() {
+        for key in get_content_keys(hook) {
+            usages.push((key, format!("the hook \"{}\"", hook)));
+        }
+    }
+
+    for injection in template.injections.iter().flatten() {
+        for key in get_content_keys(&injection.snippet) {
+            usages.push((key, format!("the injection into \"{}\"", injection.file)));
+        }
+    }
+
+    Ok(usages)
+}
+
+// The arg shapes as they are written, with their case modifiers
+pub fn get_content_shapes(content: &str) -> Vec<String> {
+    get_content_regex()
+        .shape
+        .find_iter(content)
+        .map(|shape| shape.as_str().to_string())
+        .collect()
+}
+
+fn get_content_regex() -> EngineRegex {
+    EngineRegex {
+        shape: Regex::new(r"\{>[\w|\s|-]*<}").unwrap(),
+        key: Regex::new(r"[{}><\s]").unwrap(),
+    }
+}
+
+fn get_path_regex() -> EngineRegex {
+    EngineRegex {
+        shape: Regex::new(r"\(\[[\w|\s|-]*\]\)").unwrap(),
+        key: Regex::new(r"[\(\)\[\]\s]").unwrap(),
+    }
+}
+
+fn get_keys(text: &str, regex: EngineRegex) -> Vec<String> {
+    regex
+        .shape
+        .find_iter(text)
+        .map(|shape| {
+            let key = regex.key.replace_all(shape.as_str(), "");
+            key.split('|').next().unwrap_or_default().to_string()
+        })
+        .collect()
+}
+
+// Splits the key from its case modifier
+fn split_key(key: &str) -> Result<(&str, Option<TextCase>), Error> {
+    match key.split_once('|') {
+        Some((key, case)) => Ok((key, Some(TextCase::from_name(case)?))),
+        None => Ok((key, None)),
+    }
 }
 
 fn parse(text: String, args: Vec<TempEngineArg>, regex: EngineRegex) -> Result<String, Error> {
@@ -45,15 +139,21 @@ fn parse(text: String, args: Vec<TempEngineArg>, regex: EngineRegex) -> Result<S
     for caps in regex.shape.captures_iter(&text) {
         let shape = &caps[0];
         let key = regex.key.replace_all(shape, "");
+        let (key, case) = split_key(&key)?;
         let arg = args.clone().into_iter().find(|arg| arg.key == key);
 
         if let Some(arg) = arg {
+            let value = match case {
+                Some(case) => case.apply(&arg.value),
+                None => arg.value,
+            };
+
             // format shape to use it as a regex
             let formated_shape = format_shape_string_to_reg(shape);
 
             // regex for substitute the shapes in text by key value
             let shape_regex = Regex::new(&formated_shape).unwrap();
-            final_text = shape_regex.replace_all(&final_text, &arg.value).to_string();
+            final_text = shape_regex.replace_all(&final_text, value.as_str()).to_string();
         } else {
             return Err(not_found_error(&format!(
                 "Key \"{}\" was not informed.",
@@ -65,10 +165,18 @@ fn parse(text: String, args: Vec<TempEngineArg>, regex: EngineRegex) -> Result<S
     Ok(final_text)
 }
 
-pub fn get_engine_args_input(args: &Vec<ConfigArg>) -> Result<Vec<TempEngineArg>, Error> {
+// The questions go to stderr when to_stderr is true
+pub fn get_engine_args_input(
+    args: &Vec<ConfigArg>,
+    to_stderr: bool,
+) -> Result<Vec<TempEngineArg>, Error> {
     args.into_iter()
         .map(|arg| {
-            let value = input::get(&arg.query)?;
+            let value = match (arg.secret.unwrap_or(false), to_stderr) {
+                (true, true) => rpassword::prompt_password_stderr(&arg.query)?,
+                (true, false) => rpassword::prompt_password_stdout(&arg.query)?,
+                (false, _) => input::prompt(&arg.query, to_stderr)?,
+            };
             Ok(TempEngineArg {
                 key: arg.key.to_string(),
                 value,
@@ -99,8 +207,20 @@ pub fn set_arg_default_value(
     Ok(arg)
 }
 
+// Removes the args that were declared as secret, so they can be recorded safely
+pub fn get_public_args(args: &[TempEngineArg], config_args: &[ConfigArg]) -> Vec<TempEngineArg> {
+    args.iter()
+        .filter(|arg| {
+            !config_args
+                .iter()
+                .any(|config_arg| config_arg.key == arg.key && config_arg.secret.unwrap_or(false))
+        })
+        .cloned()
+        .collect()
+}
+
 fn format_shape_string_to_reg(shape: &str) -> String {
-    Regex::new(r"(?P<symbol>[\()\[\]\{\}])")
+    Regex::new(r"(?P<symbol>[\()\[\]\{\}|])")
     .unwrap()
     .replace_all(shape, r"\$symbol")
     .to_string()
@@ -0,0 +1,100 @@
+use super::engine::{get_content_key_usages, get_path_key_usages};
+use super::{TempPath, Template};
+use crate::utils::errors::{invalid_input_error, std_error};
+use crate::utils::path::get_path_name;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::{io::Error, path::Path};
+
+// Keeps only the template paths selected by the globs (comma separated). A path
+// is selected when it or one of its parent folders matches, and the parent
+// folders of the selected paths are kept. Args not used anymore are removed.
+pub fn filter_template(
+    template: Template,
+    only: Option<&str>,
+    exclude: Option<&str>,
+) -> Result<Template, Error> {
+    let only = match only {
+        Some(globs) => Some(build_glob_set(globs)?),
+        None => None,
+    };
+    let exclude = match exclude {
+        Some(globs) => Some(build_glob_set(globs)?),
+        None => None,
+    };
+
+    let is_selected = |path: &Path| {
+        let included = match &only {
+            Some(only) => path.ancestors().any(|ancestor| only.is_match(ancestor)),
+            None => true,
+        };
+        let excluded = match &exclude {
+            Some(exclude) => path.ancestors().any(|ancestor| exclude.is_match(ancestor)),
+            None => false,
+        };
+
+        included && !excluded
+    };
+
+    let selected_paths: Vec<&TempPath> = template
+        .paths
+        .iter()
+        .filter(|path| is_selected(&path.path))
+        .collect();
+
+    if selected_paths.is_empty() {
+        return Err(invalid_input_error(
+            "No template files match the --only and --exclude globs.",
+        ));
+    }
+
+    let paths: Vec<TempPath> = template
+        .paths
+        .iter()
+        .filter(|path| {
+            selected_paths
+                .iter()
+                .any(|selected| selected.path.starts_with(&path.path))
+        })
+        .cloned()
+        .collect();
+
+    let path_names: Vec<String> = paths.iter().map(|path| get_path_name(&path.path)).collect();
+    let contents = template
+        .contents
+        .iter()
+        .filter(|content| path_names.contains(&get_path_name(Path::new(&content.file_path))))
+        .cloned()
+        .collect();
+
+    let filtered = Template {
+        paths,
+        contents,
+        ..template
+    };
+    let used_keys: Vec<String> = get_path_key_usages(&filtered)
+        .into_iter()
+        .chain(get_content_key_usages(&filtered)?)
+        .map(|(key, _)| key)
+        .collect();
+    let args = filtered.args.clone().map(|args| {
+        args.into_iter()
+            .filter(|arg| used_keys.contains(&arg.key))
+            .collect()
+    });
+
+    Ok(Template { args, ..filtered })
+}
+
+fn build_glob_set(globs: &str) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for glob in globs
+        .split(',')
+        .map(str::trim)
+        .filter(|glob| !glob.is_empty())
+    {
+        builder.add(std_error(Glob::new(glob.trim_end_matches('/')))?);
+    }
+
+    std_error(builder.build())
+}
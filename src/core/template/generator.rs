@@ -1,112 +1,224 @@
+use super::attributes::{convert_line_endings, decode_text, encode_text, get_file_attributes};
 use super::engine::parse_content;
 use super::engine::TempEngineArg;
+use super::injector::{plan_injections, write_injections};
+use super::maker::TempData;
+use super::sink::{FsSink, OutputSink};
 use super::Template;
 use crate::core::template::engine::parse_path;
 use crate::core::template::{TempContent, TempPath, TempPathType};
-use crate::utils::string::decode_base64;
+use crate::utils::errors::std_error;
 use crate::{
     paint, paintln,
-    utils::path::{format_path_namespace, pathbuf_to_string, str_to_pathbuf},
+    utils::path::{get_path_name, pathbuf_to_string, str_to_pathbuf},
 };
+use rayon::prelude::*;
 use std::{
+    collections::HashMap,
     fs,
     io::Error,
     path::{Path, PathBuf},
 };
 
+// Returns the generated paths, relative to the output directory
+// Nothing is printed when quiet. The render comes from render_template, so the
+// same render can be shared with the generation record.
 pub fn gen_template(
-    template: Template,
+    template: &Template,
+    render: TempData,
     directory: &Path,
-    temp_args: Vec<TempEngineArg>,
-) -> Result<(), Error> {
-    let template_contents: Result<Vec<TempContent>, Error> = if !temp_args.is_empty() {
-        template
-            .contents
-            .into_iter()
-            .map(|content| {
-                let text_content_parsed = base64::encode(parse_content(
-                    decode_base64(content.text)?,
-                    temp_args.clone(),
-                )?);
-                let filename_parsed = parse_path(content.file_path, temp_args.clone())?;
-                Ok(TempContent {
-                    file_path: filename_parsed,
-                    text: text_content_parsed,
-                })
-            })
-            .collect()
-    } else {
-        Ok(template.contents)
+    temp_args: &[TempEngineArg],
+    quiet: bool,
+) -> Result<Vec<TempPath>, Error> {
+    if !directory.exists() {
+        fs::create_dir_all(directory)?;
+    }
+
+    let mut sink = FsSink::new(directory);
+    if quiet {
+        sink = sink.quiet();
+    }
+
+    let injection_plan = match &template.injections {
+        Some(injections) => Some(plan_injections(injections, directory, temp_args)?),
+        None => None,
     };
 
-    let template_contents = template_contents?;
+    let paths = write_rendered_template(render, &mut sink)?;
 
-    if !directory.exists() {
-        fs::create_dir_all(directory)?;
+    if let Some(injection_plan) = injection_plan {
+        if !quiet {
+            paintln!("{gray}", "\n[injecting snippets...]");
+        }
+        write_injections(injection_plan, quiet)?;
     }
 
-    paintln!("{gray}", "[creating files and folders...]");
-    for path in template.paths.into_iter() {
-        let path = if !temp_args.is_empty() {
-            let path_parsed = parse_path(pathbuf_to_string(path.path), temp_args.clone())?;
-            TempPath {
-                path: str_to_pathbuf(&path_parsed),
-                path_type: path.path_type,
-            }
-        } else {
-            path
-        };
-        create_path(path, directory)?;
+    if !quiet {
+        println!();
     }
+    Ok(paths)
+}
 
-    if template_contents.len() > 0 {
-        paintln!("{gray}", "\n[writing contents...]");
-        write_contents(template_contents.clone(), directory)?;
+// Writes the rendered template into the output sink (folder, archive or stdout).
+// Files are rendered and written in parallel, the log keeps the template order.
+pub fn write_template(
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    sink: &mut dyn OutputSink,
+) -> Result<Vec<TempPath>, Error> {
+    write_rendered_template(render_template(template, temp_args)?, sink)
+}
+
+pub fn write_rendered_template(
+    render: TempData,
+    sink: &mut dyn OutputSink,
+) -> Result<Vec<TempPath>, Error> {
+    let TempData { paths, contents } = render;
+
+    // Folders are created in order, so they exist before their files are written
+    for path in paths.iter() {
+        if path.path_type == TempPathType::Dir {
+            sink.create_dir(&path.path)?;
+        }
+    }
+
+    let contents_by_path: HashMap<String, &TempContent> = contents
+        .iter()
+        .map(|content| (get_path_name(Path::new(&content.file_path)), content))
+        .collect();
+
+    let files: Result<Vec<(PathBuf, Vec<u8>)>, Error> = paths
+        .par_iter()
+        .filter(|path| path.path_type == TempPathType::File)
+        .map(|path| {
+            let bytes = match contents_by_path.get(&get_path_name(&path.path)) {
+                Some(content) => std_error(base64::decode(&content.text))?,
+                None => vec![],
+            };
+            Ok((path.path.clone(), bytes))
+        })
+        .collect();
+    sink.write_files(files?)?;
+
+    if sink.logs() {
+        log_generation(&paths, &contents_by_path);
     }
 
-    print!("\n");
-    Ok(())
+    sink.finish()?;
+    Ok(paths)
 }
 
-fn create_path(path: TempPath, directory: &Path) -> Result<(), Error> {
-    let real_path = TempPath {
-        path: get_real_path(directory, path.path),
-        path_type: path.path_type,
-    };
+// Renders the template paths and contents in memory, without touching the file system.
+// The contents are the bytes written by the generation, with the line endings and
+// encoding of the attributes matched by the template (not rendered) paths.
+pub fn render_template(
+    template: &Template,
+    temp_args: &[TempEngineArg],
+) -> Result<TempData, Error> {
+    let template_attributes = template.attributes.as_deref().unwrap_or_default();
+    let contents: Result<Vec<TempContent>, Error> = template
+        .contents
+        .par_iter()
+        .map(|content| {
+            let attributes = get_file_attributes(template_attributes, &content.file_path)?;
+            let bytes = std_error(base64::decode(&content.text))?;
+
+            // Binary files are not parsed by the engine
+            let text_content_parsed = match decode_text(&bytes, &attributes) {
+                Some(text) => {
+                    let text = if temp_args.is_empty() {
+                        text
+                    } else {
+                        parse_content(text, temp_args.to_vec())?
+                    };
+                    base64::encode(encode_text(
+                        &convert_line_endings(&text, &attributes.eol),
+                        &attributes,
+                        &content.file_path,
+                    )?)
+                }
+                None => content.text.clone(),
+            };
+            let filename_parsed = render_path(content.file_path.clone(), temp_args)?;
+            Ok(TempContent {
+                file_path: filename_parsed,
+                text: text_content_parsed,
+            })
+        })
+        .collect();
 
-    if real_path.path_type == TempPathType::File {
-        fs::write(&real_path.path, "")?;
-        paint!("{gray}", "file: ");
+    let paths: Result<Vec<TempPath>, Error> = template
+        .paths
+        .par_iter()
+        .map(|path| {
+            let path_parsed = render_path(pathbuf_to_string(path.path.clone()), temp_args)?;
+            Ok(TempPath {
+                path: str_to_pathbuf(&path_parsed),
+                path_type: path.path_type.clone(),
+            })
+        })
+        .collect();
+
+    Ok(TempData {
+        paths: paths?,
+        contents: contents?,
+    })
+}
+
+// Maps each rendered file path to the bytes that the generation writes
+pub fn get_rendered_files(render: &TempData) -> Result<HashMap<String, Vec<u8>>, Error> {
+    let mut files = HashMap::new();
+
+    for path in render.paths.iter() {
+        if path.path_type == TempPathType::File {
+            files.insert(get_path_name(&path.path), vec![]);
+        }
     }
-    if real_path.path_type == TempPathType::Dir {
-        fs::create_dir(&real_path.path)?;
-        paint!("{gray}", "dir:  ");
+
+    for content in render.contents.iter() {
+        let file_name = get_path_name(Path::new(&content.file_path));
+        files.insert(file_name, std_error(base64::decode(&content.text))?);
     }
 
-    println!(
-        "{}",
-        pathbuf_to_string(format_path_namespace(real_path.path))
-    );
+    Ok(files)
+}
 
-    Ok(())
+// Without args the paths are kept as they are in the template
+fn render_path(path: String, temp_args: &[TempEngineArg]) -> Result<String, Error> {
+    if temp_args.is_empty() {
+        Ok(path)
+    } else {
+        parse_path(path, temp_args.to_vec())
+    }
 }
 
-fn write_contents(contents: Vec<TempContent>, directory: &Path) -> Result<(), Error> {
-    for content in contents.into_iter() {
-        let file_path = get_real_path(directory, str_to_pathbuf(&content.file_path));
-        if file_path.exists() {
-            fs::write(&file_path, decode_base64(content.text)?)?;
+fn log_generation(paths: &[TempPath], contents_by_path: &HashMap<String, &TempContent>) {
+    paintln!("{gray}", "[creating files and folders...]");
+    for path in paths.iter() {
+        log_path_created(path);
+    }
 
-            print!("{}", pathbuf_to_string(format_path_namespace(file_path)));
-            paintln!("...{green}", "ok");
+    if !contents_by_path.is_empty() {
+        paintln!("{gray}", "\n[writing contents...]");
+    }
+    for path in paths.iter() {
+        if contents_by_path.contains_key(&get_path_name(&path.path)) {
+            log_content_written(&path.path);
         }
     }
+}
+
+fn log_path_created(path: &TempPath) {
+    let label = match path.path_type {
+        TempPathType::File => "file: ",
+        TempPathType::Dir => "dir:  ",
+    };
 
-    Ok(())
+    paint!("{gray}", label);
+    println!("{}", get_path_name(&path.path));
 }
 
-fn get_real_path(directory: &Path, path: PathBuf) -> PathBuf {
-    Path::new(directory)
-        .join(pathbuf_to_string(path))
-        .to_path_buf()
+fn log_content_written(file_path: &Path) {
+    print!("{}", get_path_name(file_path));
+    paintln!("...{green}", "ok");
 }
@@ -0,0 +1,75 @@
+use super::archive::{read_tar_stream, ArchiveFiles};
+use crate::utils::errors::{invalid_input_error, not_found_error};
+use std::{
+    io::Error,
+    path::Path,
+    process::{Command, Output},
+};
+
+// Gets the commit hash of a revision (commit, branch or tag) of a local repository
+pub fn resolve_git_rev(repo_path: &str, rev: &str) -> Result<String, Error> {
+    let output = run_git(
+        repo_path,
+        &["rev-parse", "--verify", &format!("{}^{{commit}}", rev)],
+    )?;
+
+    if !output.status.success() {
+        return Err(not_found_error(&format!(
+            "Revision \"{}\" was not found in the \"{}\" git repository.",
+            rev, repo_path
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Reads the files tracked at the commit in memory, as they are in the commit
+// and not as they are in the working tree.
+pub fn read_git_commit(repo_path: &str, commit: &str) -> Result<ArchiveFiles, Error> {
+    let output = run_git(repo_path, &["archive", "--format=tar", commit])?;
+
+    if !output.status.success() {
+        return Err(invalid_input_error(&format!(
+            "Not is possible to read the commit \"{}\": {}",
+            commit,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    // Symbolic links and submodules are not saved in the template
+    read_tar_stream(&output.stdout)
+}
+
+// Text of a file at the commit, None when the commit does not have the file
+pub fn read_git_file(
+    repo_path: &str,
+    commit: &str,
+    file_path: &str,
+) -> Result<Option<String>, Error> {
+    let output = run_git(repo_path, &["show", &format!("{}:{}", commit, file_path)])?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+}
+
+fn run_git(repo_path: &str, args: &[&str]) -> Result<Output, Error> {
+    if !Path::new(repo_path).is_dir() {
+        return Err(not_found_error(&format!(
+            "The git repository \"{}\" was not found.",
+            repo_path
+        )));
+    }
+
+    match Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+    {
+        Ok(output) => Ok(output),
+        Err(_) => Err(not_found_error("\"git\" was not found on PATH.")),
+    }
+}
@@ -0,0 +1,169 @@
+use super::engine::{get_content_shapes, parse_content, TempEngineArg};
+use crate::core::info::os_is_windows;
+use crate::paintln;
+use crate::utils::errors::other_error;
+use std::{
+    io::{stderr, Error},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+// Runs the template post-generation hooks inside the output directory.
+// Hook output is inherited, so it is streamed straight to the console.
+// When quiet, the hook output goes to stderr and stdout is kept clean.
+pub fn run_hooks(
+    hooks: &[String],
+    directory: &Path,
+    temp_args: &[TempEngineArg],
+    quiet: bool,
+) -> Result<(), Error> {
+    // Rendered before running any hook, so a missing arg does not run half of them
+    let hooks: Vec<(String, HookScript)> = render_hooks(hooks, temp_args)?
+        .into_iter()
+        .zip(hooks.iter())
+        .map(|(rendered, hook)| Ok((rendered, get_hook_script(hook, temp_args)?)))
+        .collect::<Result<_, Error>>()?;
+
+    if !quiet {
+        paintln!("{gray}", "[running hooks...]");
+    }
+    for (rendered, script) in hooks.iter() {
+        run_hook(rendered, script, directory, quiet)?;
+    }
+
+    if !quiet {
+        println!();
+    }
+    Ok(())
+}
+
+fn run_hook(hook: &str, script: &HookScript, directory: &Path, quiet: bool) -> Result<(), Error> {
+    let mut command = shell_command(&script.text);
+    command
+        .current_dir(directory)
+        .envs(script.env.iter().cloned());
+
+    if quiet {
+        command.stdout(Stdio::from(stderr()));
+    } else {
+        paintln!("{gray}", format!("$ {}", hook));
+    }
+
+    let status = command.status()?;
+    if !status.success() {
+        return Err(other_error(&format!(
+            "Hook \"{}\" failed ({}).",
+            hook, status
+        )));
+    }
+
+    Ok(())
+}
+
+// Shell script of a hook and the environment variables with its arg values
+#[derive(Debug, PartialEq, Clone)]
+pub struct HookScript {
+    pub text: String,
+    pub env: Vec<(String, String)>,
+}
+
+// The arg values are passed to the shell as environment variables and never as
+// part of the script, so a value like "x; rm -rf ~" is not run as a command.
+// Each placeholder is replaced by a variable reference that is valid in its
+// quoting, so '{> arg <}' and "{> arg <}" keep the value as a single word.
+pub fn get_hook_script(hook: &str, temp_args: &[TempEngineArg]) -> Result<HookScript, Error> {
+    let shapes = get_content_shapes(hook);
+    let mut shape_names: Vec<(String, String)> = vec![];
+    let mut env = vec![];
+    let mut text = String::new();
+    let mut quote = ShellQuote::None;
+    let mut rest = hook;
+
+    while let Some(ch) = rest.chars().next() {
+        if let Some(shape) = shapes.iter().find(|shape| rest.starts_with(shape.as_str())) {
+            let name = match shape_names.iter().find(|(other, _)| other == shape) {
+                Some((_, name)) => name.clone(),
+                None => {
+                    let name = format!("TEMPLO_ARG_{}", env.len());
+                    env.push((
+                        name.clone(),
+                        parse_content(shape.clone(), temp_args.to_vec())?,
+                    ));
+                    shape_names.push((shape.clone(), name.clone()));
+                    name
+                }
+            };
+
+            text.push_str(&shell_variable(&name, quote));
+            rest = &rest[shape.len()..];
+            continue;
+        }
+
+        let mut escaped = ch.to_string();
+        if ch == '\\' && quote != ShellQuote::Single {
+            escaped.extend(rest[1..].chars().next());
+        } else {
+            quote = quote.next(ch);
+        }
+
+        text.push_str(&escaped);
+        rest = &rest[escaped.len()..];
+    }
+
+    Ok(HookScript { text, env })
+}
+
+// Hooks are displayed with the same shape as file contents: {> arg <}
+pub fn render_hooks(hooks: &[String], temp_args: &[TempEngineArg]) -> Result<Vec<String>, Error> {
+    hooks
+        .iter()
+        .map(|hook| parse_content(hook.clone(), temp_args.to_vec()))
+        .collect()
+}
+
+// The sh quoting at a position of the hook
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ShellQuote {
+    None,
+    Single,
+    Double,
+}
+
+impl ShellQuote {
+    fn next(self, ch: char) -> Self {
+        match (self, ch) {
+            (Self::None, '\'') => Self::Single,
+            (Self::None, '"') => Self::Double,
+            (Self::Single, '\'') | (Self::Double, '"') => Self::None,
+            _ => self,
+        }
+    }
+}
+
+// The variables are expanded after the script is parsed, so their values are
+// never run: sh expands them as a single word, and cmd expands them delayed
+// (/V:ON), after its special chars were handled.
+fn shell_variable(name: &str, quote: ShellQuote) -> String {
+    if os_is_windows() {
+        return format!("!{}!", name);
+    }
+
+    match quote {
+        ShellQuote::None => format!("\"${{{}}}\"", name),
+        ShellQuote::Double => format!("${{{}}}", name),
+        // Single quotes are closed around the variable, they do not expand it
+        ShellQuote::Single => format!("'\"${{{}}}\"'", name),
+    }
+}
+
+fn shell_command(hook: &str) -> Command {
+    if os_is_windows() {
+        let mut command = Command::new("cmd");
+        command.args(["/V:ON", "/C", hook]);
+        return command;
+    }
+
+    let mut command = Command::new("sh");
+    command.args(["-c", hook]);
+    command
+}
@@ -0,0 +1,128 @@
+use super::composer::{compose_templates, ConflictPolicy};
+use super::config::ConfigArg;
+use super::Template;
+use crate::cli::input::namespaces::{get_repo_namespace_obj, NamespaceObject};
+use crate::core::repos::Repository;
+use crate::utils::errors::invalid_input_error;
+use crate::utils::path::{get_path_name, normalize_path, pathbuf_to_string};
+use std::{io::Error, path::Path};
+
+// Applies the template over the base templates it extends, getting them from the repositories
+pub fn resolve_template(template: Template) -> Result<Template, Error> {
+    resolve_template_with(template, &get_repo_template)
+}
+
+pub fn resolve_template_with(
+    template: Template,
+    get_template: &dyn Fn(&str) -> Result<Template, Error>,
+) -> Result<Template, Error> {
+    resolve(template, get_template, &mut vec![])
+}
+
+fn resolve(
+    template: Template,
+    get_template: &dyn Fn(&str) -> Result<Template, Error>,
+    visited: &mut Vec<String>,
+) -> Result<Template, Error> {
+    let extends = match &template.extends {
+        Some(extends) => extends.clone(),
+        None => return Ok(template),
+    };
+
+    if visited.contains(&extends.template) {
+        return Err(invalid_input_error(&format!(
+            "Template \"{}\" extends itself through \"{}\".",
+            template.name,
+            visited.join("\" -> \"")
+        )));
+    }
+    visited.push(extends.template.clone());
+
+    let base = resolve(get_template(&extends.template)?, get_template, visited)?;
+    let deleted_paths: Vec<String> = extends
+        .delete
+        .iter()
+        .flatten()
+        .map(|path| get_path_name(&normalize_path(Path::new(path))))
+        .collect();
+
+    let is_inherited = |path: &str| {
+        let path = get_path_name(&normalize_path(Path::new(path)));
+        !deleted_paths
+            .iter()
+            .any(|deleted| path == *deleted || path.starts_with(&format!("{}/", deleted)))
+    };
+
+    let base = Template {
+        paths: base
+            .paths
+            .into_iter()
+            .filter(|path| is_inherited(&pathbuf_to_string(path.path.clone())))
+            .collect(),
+        contents: base
+            .contents
+            .into_iter()
+            .filter(|content| is_inherited(&content.file_path))
+            .collect(),
+        ..base
+    };
+    let args = override_args(base.args.clone(), template.args.clone());
+
+    // The child files override the inherited ones
+    let child = Template {
+        extends: None,
+        ..template.clone()
+    };
+    let composed = compose_templates(vec![base, child], &ConflictPolicy::Override)?;
+
+    Ok(Template {
+        paths: composed.paths,
+        contents: composed.contents,
+        args,
+        hooks: composed.hooks,
+        requirements: composed.requirements,
+        injections: composed.injections,
+        attributes: composed.attributes,
+        extends: None,
+        // The base hooks run too, so a remote base makes the template remote
+        source: composed.source,
+        ..template
+    })
+}
+
+fn override_args(
+    base_args: Option<Vec<ConfigArg>>,
+    child_args: Option<Vec<ConfigArg>>,
+) -> Option<Vec<ConfigArg>> {
+    let child_args = match child_args {
+        Some(child_args) => child_args,
+        None => return base_args,
+    };
+
+    let mut args: Vec<ConfigArg> = base_args
+        .into_iter()
+        .flatten()
+        .map(
+            |arg| match child_args.iter().find(|child_arg| child_arg.key == arg.key) {
+                Some(child_arg) => child_arg.clone(),
+                None => arg,
+            },
+        )
+        .collect();
+
+    for child_arg in child_args.into_iter() {
+        if !args.iter().any(|arg| arg.key == child_arg.key) {
+            args.push(child_arg);
+        }
+    }
+
+    Some(args)
+}
+
+fn get_repo_template(namespace: &str) -> Result<Template, Error> {
+    let NamespaceObject {
+        repo_name,
+        template_name,
+    } = get_repo_namespace_obj(namespace);
+    Repository::connect(repo_name)?.get_template(&template_name)
+}
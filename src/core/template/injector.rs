@@ -0,0 +1,172 @@
+use super::config::{ConfigInjection, InjectionPosition};
+use super::engine::{parse_content, parse_path, TempEngineArg};
+use crate::utils::errors::{invalid_input_error, not_found_error, std_error};
+use crate::utils::path::{format_path_namespace, normalize_path, pathbuf_to_string};
+use crate::{paint, paintln};
+use regex::Regex;
+use std::{
+    fs,
+    io::Error,
+    path::{Component, Path, PathBuf},
+};
+
+// Snippets rendered into the text of their files, ready to be written
+pub struct InjectionPlan {
+    files: Vec<(PathBuf, String)>,
+    logs: Vec<(String, bool)>,
+}
+
+// Every injection is checked and rendered in memory before anything is written,
+// so a missing file or marker does not leave a half generated directory.
+// The injected files must already exist inside of the output directory.
+pub fn plan_injections(
+    injections: &[ConfigInjection],
+    directory: &Path,
+    temp_args: &[TempEngineArg],
+) -> Result<InjectionPlan, Error> {
+    let mut files: Vec<(PathBuf, String)> = vec![];
+    let mut logs = vec![];
+
+    for injection in injections.iter() {
+        let relative_path = get_injection_path(injection, temp_args)?;
+        let file_path = directory.join(&relative_path);
+        let file_name = pathbuf_to_string(format_path_namespace(relative_path));
+
+        if !file_path.is_file() {
+            return Err(not_found_error(&format!(
+                "Not is possible to inject a snippet into \"{}\", file not exists.",
+                file_name
+            )));
+        }
+
+        // Many snippets can be injected into the same file
+        let file_idx = match files.iter().position(|(path, _)| *path == file_path) {
+            Some(idx) => idx,
+            None => {
+                files.push((file_path.clone(), fs::read_to_string(&file_path)?));
+                files.len() - 1
+            }
+        };
+
+        let snippet = parse_content(injection.snippet.clone(), temp_args.to_vec())?;
+        let injected = match insert_snippet(&files[file_idx].1, &snippet, injection)? {
+            Some(injected_text) => {
+                files[file_idx].1 = injected_text;
+                true
+            }
+            None => false,
+        };
+        logs.push((file_name, injected));
+    }
+
+    Ok(InjectionPlan { files, logs })
+}
+
+pub fn write_injections(plan: InjectionPlan, quiet: bool) -> Result<(), Error> {
+    for (file_path, text) in plan.files.iter() {
+        fs::write(file_path, text)?;
+    }
+
+    if !quiet {
+        plan.logs
+            .iter()
+            .for_each(|(file_name, injected)| display_injection(file_name, *injected));
+    }
+
+    Ok(())
+}
+
+fn display_injection(file_name: &str, injected: bool) {
+    paint!("{gray}", "inject: ");
+    print!("{}", file_name);
+
+    if injected {
+        paintln!("...{green}", "ok");
+    } else {
+        paintln!("...{gray}", "already injected");
+    }
+}
+
+// Rendered path of the injected file, relative to the output directory
+fn get_injection_path(
+    injection: &ConfigInjection,
+    temp_args: &[TempEngineArg],
+) -> Result<PathBuf, Error> {
+    let path = normalize_path(Path::new(&parse_path(
+        injection.file.clone(),
+        temp_args.to_vec(),
+    )?));
+    let is_inside = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+
+    if !is_inside || path.as_os_str().is_empty() {
+        return Err(invalid_input_error(&format!(
+            "Not is possible to inject a snippet into \"{}\", the file must be inside of the output directory.",
+            injection.file
+        )));
+    }
+
+    Ok(path)
+}
+
+// Returns None when the snippet is already in the text, so running the
+// same generation twice does not insert it again.
+pub fn insert_snippet(
+    text: &str,
+    snippet: &str,
+    injection: &ConfigInjection,
+) -> Result<Option<String>, Error> {
+    let marker_regex = if injection.regex.unwrap_or(false) {
+        std_error(Regex::new(&injection.marker))?
+    } else {
+        std_error(Regex::new(&regex::escape(&injection.marker)))?
+    };
+
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let marker_idx = lines.iter().position(|line| marker_regex.is_match(line));
+
+    let marker_idx = match marker_idx {
+        Some(idx) => idx,
+        None => {
+            return Err(not_found_error(&format!(
+                "Marker \"{}\" was not found in \"{}\".",
+                injection.marker, injection.file
+            )))
+        }
+    };
+
+    // The snippet is indented like the marker line
+    let marker_line = lines[marker_idx];
+    let indent: String = marker_line
+        .chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .collect();
+    let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let snippet_block: String = snippet
+        .lines()
+        .map(|line| format!("{}{}{}", indent, line, newline))
+        .collect();
+
+    if text.contains(snippet_block.trim_end()) {
+        return Ok(None);
+    }
+
+    let mut injected_text = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if idx == marker_idx && injection.position == Some(InjectionPosition::Before) {
+            injected_text.push_str(&snippet_block);
+        }
+
+        injected_text.push_str(line);
+
+        if idx == marker_idx && injection.position != Some(InjectionPosition::Before) {
+            if !line.ends_with('\n') {
+                injected_text.push_str(newline);
+            }
+            injected_text.push_str(&snippet_block);
+        }
+    }
+
+    Ok(Some(injected_text))
+}
@@ -0,0 +1,250 @@
+use super::config::{
+    parse_config_manifest, ConfigAllowedSecret, ConfigArg, ConfigAttribute, ConfigExtends,
+    ConfigInjection, ConfigRequirement, CONFIG_MANIFEST_FILENAMES,
+};
+use super::engine::{
+    get_content_key_usages, get_path_key_usages, parse_path, KeyUsage, TempEngineArg,
+};
+use super::inheritance::resolve_template;
+use super::maker::make_template;
+use super::size::TempSizeLimits;
+use super::Template;
+use crate::utils::path::pathbuf_to_string;
+use serde::de::DeserializeOwned;
+use serde_json::{from_str, Value};
+use std::{
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+};
+
+const INVALID_PATH_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LintLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LintIssue {
+    pub level: LintLevel,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn error(message: String) -> Self {
+        Self {
+            level: LintLevel::Error,
+            message,
+        }
+    }
+
+    fn warning(message: String) -> Self {
+        Self {
+            level: LintLevel::Warning,
+            message,
+        }
+    }
+}
+
+// The template can only be read when its config files are valid, so the
+// malformed config files are the only issues reported in that case.
+pub fn lint_directory(directory: &str) -> Result<Vec<LintIssue>, Error> {
+    let config_issues = lint_config_files(directory)?;
+
+    if !config_issues.is_empty() {
+        return Ok(config_issues);
+    }
+
+    // The size limits are only checked when the template is saved
+    let template = make_template(
+        "lint".to_string(),
+        directory,
+        None,
+        true,
+        &[],
+        &TempSizeLimits::unlimited(),
+    )?;
+    lint_template(&resolve_template(template)?)
+}
+
+pub fn lint_template(template: &Template) -> Result<Vec<LintIssue>, Error> {
+    let args = template.args.clone().unwrap_or_default();
+    let content_keys = get_content_key_usages(template)?;
+    let path_keys = get_path_key_usages(template);
+    let used_keys: Vec<&KeyUsage> = path_keys.iter().chain(content_keys.iter()).collect();
+
+    let mut issues = vec![];
+
+    for (idx, arg) in args.iter().enumerate() {
+        if args[..idx].iter().any(|previous| previous.key == arg.key) {
+            issues.push(LintIssue::error(format!(
+                "Arg \"{}\" is declared more than once.",
+                arg.key
+            )));
+        }
+    }
+
+    let mut undeclared_keys: Vec<&str> = vec![];
+    for (key, location) in used_keys.iter() {
+        if args.iter().any(|arg| arg.key == *key) || undeclared_keys.contains(&key.as_str()) {
+            continue;
+        }
+
+        undeclared_keys.push(key);
+        issues.push(LintIssue::error(format!(
+            "Key \"{}\" is used in {} but is not declared as an arg.",
+            key, location
+        )));
+    }
+
+    for arg in args.iter() {
+        if !used_keys.iter().any(|(key, _)| *key == arg.key) {
+            issues.push(LintIssue::warning(format!(
+                "Arg \"{}\" is declared but is not used.",
+                arg.key
+            )));
+        }
+    }
+
+    for arg in args.iter() {
+        let default = match &arg.default {
+            Some(default) => default,
+            None => continue,
+        };
+
+        if path_keys.iter().any(|(key, _)| *key == arg.key) && !is_valid_path_value(default) {
+            issues.push(LintIssue::error(format!(
+                "Default value \"{}\" of arg \"{}\" is not valid in a path.",
+                default, arg.key
+            )));
+        }
+    }
+
+    issues.append(&mut get_path_collisions(template, &args, &path_keys)?);
+
+    Ok(issues)
+}
+
+fn lint_config_files(directory: &str) -> Result<Vec<LintIssue>, Error> {
+    let mut issues = vec![];
+
+    for filename in CONFIG_MANIFEST_FILENAMES.iter() {
+        let manifest_path = Path::new(directory).join(filename);
+
+        if !manifest_path.is_file() {
+            continue;
+        }
+
+        let text = fs::read_to_string(manifest_path)?;
+        // The parse error already names the manifest file
+        if let Err(err) = parse_config_manifest(filename, &text) {
+            issues.push(LintIssue::error(err.to_string()));
+        }
+    }
+
+    let config_path = Path::new(directory).join("TemplateConfig");
+
+    if !config_path.is_dir() {
+        return Ok(issues);
+    }
+
+    let mut config_files = fs::read_dir(&config_path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, Error>>()?;
+    config_files.sort();
+
+    for config_file in config_files.iter() {
+        let filename = match config_file.file_name() {
+            Some(filename) => filename.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        if !filename.ends_with(".json") {
+            continue;
+        }
+
+        let text = fs::read_to_string(config_file)?;
+        let result = match filename.as_str() {
+            "args.json" => check_json::<Vec<ConfigArg>>(&text),
+            "attributes.json" => check_json::<Vec<ConfigAttribute>>(&text),
+            "extends.json" => check_json::<ConfigExtends>(&text),
+            "hooks.json" | "ignore.json" => check_json::<Vec<String>>(&text),
+            "injections.json" => check_json::<Vec<ConfigInjection>>(&text),
+            "requirements.json" => check_json::<Vec<ConfigRequirement>>(&text),
+            "secrets.json" => check_json::<Vec<ConfigAllowedSecret>>(&text),
+            _ => check_json::<Value>(&text),
+        };
+
+        // The serde error message has the line and column of the problem
+        if let Err(err) = result {
+            issues.push(LintIssue::error(format!(
+                "TemplateConfig/{} is malformed: {}.",
+                filename, err
+            )));
+        }
+    }
+
+    Ok(issues)
+}
+
+fn check_json<T: DeserializeOwned>(text: &str) -> Result<(), serde_json::Error> {
+    from_str::<T>(text).map(|_| ())
+}
+
+fn is_valid_path_value(value: &str) -> bool {
+    !value.trim().is_empty() && value != "." && value != ".." && !value.contains(INVALID_PATH_CHARS)
+}
+
+// Renders the paths with the default values (or the key itself when the arg has
+// no default) and compares them ignoring the case, as Windows and macOS do.
+fn get_path_collisions(
+    template: &Template,
+    args: &[ConfigArg],
+    path_keys: &[KeyUsage],
+) -> Result<Vec<LintIssue>, Error> {
+    let temp_args: Vec<TempEngineArg> = path_keys
+        .iter()
+        .map(|(key, _)| TempEngineArg {
+            key: key.clone(),
+            value: args
+                .iter()
+                .find(|arg| arg.key == *key)
+                .and_then(|arg| arg.default.clone())
+                .unwrap_or_else(|| key.clone()),
+        })
+        .collect();
+
+    let mut rendered_paths: Vec<(String, String)> = vec![];
+    let mut issues = vec![];
+
+    for path in template.paths.iter() {
+        let path_name = pathbuf_to_string(path.path.clone());
+        let rendered = match parse_path(path_name.clone(), temp_args.clone()) {
+            Ok(rendered) => rendered,
+            // Like an invalid case modifier, the other paths are still checked
+            Err(err) => {
+                issues.push(LintIssue::error(format!(
+                    "Path \"{}\" cannot be rendered: {}",
+                    path_name, err
+                )));
+                continue;
+            }
+        };
+
+        let collision = rendered_paths
+            .iter()
+            .find(|(_, other)| other.to_lowercase() == rendered.to_lowercase());
+
+        match collision {
+            Some((other_path, _)) => issues.push(LintIssue::error(format!(
+                "Paths \"{}\" and \"{}\" collide when rendered as \"{}\".",
+                other_path, path_name, rendered
+            ))),
+            None => rendered_paths.push((path_name, rendered)),
+        }
+    }
+
+    Ok(issues)
+}
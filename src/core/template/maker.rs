@@ -1,8 +1,18 @@
-use super::config::get_config_args;
-use super::{miner, TempContent, TempPath, Template};
+use super::archive::{
+    get_archive_config, get_archive_manifest, is_archive_path, mine_archive_data,
+    mine_git_archive_data, read_archive, ArchiveFiles,
+};
+use super::config::{
+    get_config_file, get_config_manifest, parse_config_manifest, ConfigAllowedSecret,
+    ConfigManifest, CONFIG_MANIFEST_FILENAMES,
+};
+use super::git::{read_git_commit, read_git_file, resolve_git_rev};
+use super::replacer::{replace_literals, TempReplacement};
+use super::size::{check_size_limits, TempSizeLimits};
+use super::{miner, TempContent, TempPath, TempSource, Template};
 use crate::utils::date::get_date_now_string;
-use crate::utils::errors::invalid_input_error;
-use crate::utils::path::{format_path_namespace, pathbuf_to_string, remove_dir_prefix};
+use crate::utils::errors::{invalid_input_error, std_error};
+use crate::utils::path::{format_path_namespace, remove_dir_prefix};
 use std::io::Error;
 
 #[derive(Debug, PartialEq)]
@@ -15,49 +25,234 @@ pub fn make_template(
     temp_name: String,
     ref_path: &str,
     description: Option<String>,
+    gitignore: bool,
+    replacements: &[TempReplacement],
+    limits: &TempSizeLimits,
 ) -> Result<Template, Error> {
     if temp_name.contains(" ") {
         return Err(invalid_input_error("The template name cannot have whitespaces."))
     }
 
+    if is_archive_path(ref_path) {
+        return make_template_from_archive(
+            temp_name,
+            ref_path,
+            description,
+            gitignore,
+            replacements,
+            limits,
+        );
+    }
+
     let (name, created_at) = make_template_metadata(temp_name)?;
-    let data = make_template_data(ref_path)?;
-    let args = get_config_args(ref_path)?;
+    let data = make_template_data(ref_path, gitignore)?;
+    check_size_limits(&data.contents, limits)?;
+    let manifest = get_config_manifest(ref_path)?.unwrap_or_default();
+    let args = or_config(manifest.args, || get_config_file(ref_path, "args.json"))?;
+    let hooks = or_config(manifest.hooks, || get_config_file(ref_path, "hooks.json"))?;
+    let requirements = or_config(manifest.requirements, || {
+        get_config_file(ref_path, "requirements.json")
+    })?;
+    let injections = or_config(manifest.injections, || {
+        get_config_file(ref_path, "injections.json")
+    })?;
+    let extends = or_config(manifest.extends, || {
+        get_config_file(ref_path, "extends.json")
+    })?;
+    let attributes = or_config(manifest.attributes, || {
+        get_config_file(ref_path, "attributes.json")
+    })?;
 
-    Ok(Template {
+    let template = Template {
         name,
-        description,
+        description: description.or(manifest.description),
         created_at,
         updated_at: None,
         paths: data.paths,
         contents: data.contents,
         args,
+        hooks,
+        requirements,
+        injections,
+        extends,
+        attributes,
+        source: None,
+    };
+
+    replace_literals(template, replacements)
+}
+
+// Manifest of a template folder or archive
+pub fn get_template_manifest(ref_path: &str) -> Result<Option<ConfigManifest>, Error> {
+    if is_archive_path(ref_path) {
+        get_archive_manifest(&read_archive(ref_path)?)
+    } else {
+        get_config_manifest(ref_path)
+    }
+}
+
+// Secrets allowed by the manifest or TemplateConfig/secrets.json of a folder or archive
+pub fn get_template_allowed_secrets(ref_path: &str) -> Result<Vec<ConfigAllowedSecret>, Error> {
+    let secrets = if is_archive_path(ref_path) {
+        let files = read_archive(ref_path)?;
+        let manifest = get_archive_manifest(&files)?.unwrap_or_default();
+        or_config(manifest.secrets, || {
+            get_archive_config(&files, "secrets.json")
+        })?
+    } else {
+        let manifest = get_config_manifest(ref_path)?.unwrap_or_default();
+        or_config(manifest.secrets, || {
+            get_config_file(ref_path, "secrets.json")
+        })?
+    };
+
+    Ok(secrets.unwrap_or_default())
+}
+
+// Same as get_template_allowed_secrets, with the files at the git revision
+pub fn get_git_template_allowed_secrets(
+    repo_path: &str,
+    rev: &str,
+) -> Result<Vec<ConfigAllowedSecret>, Error> {
+    let commit = resolve_git_rev(repo_path, rev)?;
+
+    for filename in CONFIG_MANIFEST_FILENAMES.iter() {
+        if let Some(text) = read_git_file(repo_path, &commit, filename)? {
+            if let Some(secrets) = parse_config_manifest(filename, &text)?.secrets {
+                return Ok(secrets);
+            }
+        }
+    }
+
+    match read_git_file(repo_path, &commit, "TemplateConfig/secrets.json")? {
+        Some(text) => std_error(serde_json::from_str(&text)),
+        None => Ok(vec![]),
+    }
+}
+
+// The archive entries are read in memory, without extracting them
+fn make_template_from_archive(
+    temp_name: String,
+    archive_path: &str,
+    description: Option<String>,
+    gitignore: bool,
+    replacements: &[TempReplacement],
+    limits: &TempSizeLimits,
+) -> Result<Template, Error> {
+    let files = read_archive(archive_path)?;
+    let data = mine_archive_data(&files, gitignore)?;
+    let template = make_template_from_files(temp_name, &files, data, description, limits)?;
+
+    replace_literals(template, replacements)
+}
+
+// The files tracked at the revision are read in memory from "git archive", like
+// the archive entries. Only the TemplateConfig ignore patterns are applied, the
+// tracked files are never gitignored.
+pub fn make_template_from_git(
+    temp_name: String,
+    repo_path: &str,
+    rev: &str,
+    description: Option<String>,
+    replacements: &[TempReplacement],
+    limits: &TempSizeLimits,
+) -> Result<Template, Error> {
+    if temp_name.contains(' ') {
+        return Err(invalid_input_error("The template name cannot have whitespaces."));
+    }
+
+    let commit = resolve_git_rev(repo_path, rev)?;
+    let files = read_git_commit(repo_path, &commit)?;
+    let data = mine_git_archive_data(&files)?;
+    let template = make_template_from_files(temp_name, &files, data, description, limits)?;
+
+    replace_literals(
+        Template {
+            source: Some(TempSource::Git {
+                repository: repo_path.to_string(),
+                rev: rev.to_string(),
+                commit,
+            }),
+            ..template
+        },
+        replacements,
+    )
+}
+
+// The config is read from the TemplateConfig folder or the manifest in the files
+fn make_template_from_files(
+    temp_name: String,
+    files: &ArchiveFiles,
+    data: TempData,
+    description: Option<String>,
+    limits: &TempSizeLimits,
+) -> Result<Template, Error> {
+    let (name, created_at) = make_template_metadata(temp_name)?;
+    check_size_limits(&data.contents, limits)?;
+    let manifest = get_archive_manifest(files)?.unwrap_or_default();
+
+    Ok(Template {
+        name,
+        description: description.or(manifest.description),
+        created_at,
+        updated_at: None,
+        paths: data.paths,
+        contents: data.contents,
+        args: or_config(manifest.args, || get_archive_config(files, "args.json"))?,
+        hooks: or_config(manifest.hooks, || get_archive_config(files, "hooks.json"))?,
+        requirements: or_config(manifest.requirements, || {
+            get_archive_config(files, "requirements.json")
+        })?,
+        injections: or_config(manifest.injections, || {
+            get_archive_config(files, "injections.json")
+        })?,
+        extends: or_config(manifest.extends, || get_archive_config(files, "extends.json"))?,
+        attributes: or_config(manifest.attributes, || {
+            get_archive_config(files, "attributes.json")
+        })?,
+        source: None,
     })
 }
 
-pub fn make_template_data(dir_path: &str) -> Result<TempData, Error> {
-    let raw_paths = miner::mine_paths_from(dir_path)?;
+pub fn make_template_data(dir_path: &str, gitignore: bool) -> Result<TempData, Error> {
+    let raw_paths = miner::mine_paths_from(dir_path, gitignore)?;
 
-    let files = miner::mine_files_from_paths(raw_paths.clone(), dir_path)
+    let files = miner::mine_files_from_paths(raw_paths.clone(), dir_path)?
         .into_iter()
         .filter(|file| file.text != "")
         .collect();
 
-    let formatted_paths: Vec<TempPath> = raw_paths
-        .into_iter()
-        .map(|path| TempPath {
+    let mut formatted_paths: Vec<TempPath> = vec![];
+    for path in raw_paths.into_iter() {
+        let path = remove_dir_prefix(path, dir_path)?;
+
+        if path.path.as_os_str().is_empty() {
+            continue;
+        }
+
+        formatted_paths.push(TempPath {
             path: format_path_namespace(path.path),
             path_type: path.path_type,
-        })
-        .map(|path| remove_dir_prefix(path, dir_path).unwrap())
-        .filter(|path| pathbuf_to_string(path.path.clone()) != "")
-        .collect();
+        });
+    }
+
     Ok(TempData {
         paths: formatted_paths,
         contents: files,
     })
 }
 
+// The manifest setting, when it is set, otherwise the TemplateConfig file one
+fn or_config<T>(
+    manifest_config: Option<T>,
+    get_config: impl FnOnce() -> Result<Option<T>, Error>,
+) -> Result<Option<T>, Error> {
+    match manifest_config {
+        Some(config) => Ok(Some(config)),
+        None => get_config(),
+    }
+}
+
 type TempMetadata = (String, String);
 fn make_template_metadata(temp_name: String) -> Result<TempMetadata, Error> {
     let created_at = get_date_now_string();
@@ -0,0 +1,82 @@
+use super::engine::{get_public_args, TempEngineArg};
+use super::{TempPath, TempPathType, Template};
+use crate::utils::date::get_date_now_string;
+use crate::utils::errors::{not_found_error, std_error};
+use crate::utils::hash::sha256_hex;
+use crate::utils::path::{format_path_namespace, pathbuf_to_string};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{from_str, to_string_pretty};
+use std::{fs, io::Error, path::Path};
+
+pub const MANIFEST_FILENAME: &str = ".templo.json";
+
+// Record of a generation, written in the output directory
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GenManifest {
+    pub template: String,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub template_hash: String,
+    pub generated_at: String,
+    pub args: Vec<TempEngineArg>,
+    pub files: Vec<GenManifestFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GenManifestFile {
+    pub path: String,
+    pub hash: String,
+}
+
+pub fn make_manifest(
+    template_namespace: &str,
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+    generated_paths: &[TempPath],
+) -> Result<GenManifest, Error> {
+    let args = match &template.args {
+        Some(config_args) => get_public_args(temp_args, config_args),
+        None => temp_args.to_vec(),
+    };
+
+    let files: Result<Vec<GenManifestFile>, Error> = generated_paths
+        .iter()
+        .filter(|path| path.path_type == TempPathType::File)
+        .map(|path| {
+            let bytes = fs::read(directory.join(&path.path))?;
+            Ok(GenManifestFile {
+                path: pathbuf_to_string(format_path_namespace(path.path.clone())),
+                hash: sha256_hex(&bytes),
+            })
+        })
+        .collect();
+
+    Ok(GenManifest {
+        template: template_namespace.to_string(),
+        created_at: template.created_at.clone(),
+        updated_at: template.updated_at.clone(),
+        template_hash: template.hash()?,
+        generated_at: get_date_now_string(),
+        args,
+        files: files?,
+    })
+}
+
+pub fn write_manifest(directory: &Path, manifest: &GenManifest) -> Result<(), Error> {
+    let manifest_string = std_error(to_string_pretty(manifest))?;
+    fs::write(directory.join(MANIFEST_FILENAME), manifest_string)
+}
+
+pub fn read_manifest(directory: &Path) -> Result<GenManifest, Error> {
+    let manifest_filename = directory.join(MANIFEST_FILENAME);
+
+    if !manifest_filename.exists() {
+        return Err(not_found_error(&format!(
+            "Not is possible to find a generation manifest in \"{}\".",
+            pathbuf_to_string(directory.to_path_buf())
+        )));
+    }
+
+    std_error(from_str(&fs::read_to_string(manifest_filename)?))
+}
@@ -1,64 +1,126 @@
+use super::config::{get_config_manifest, CONFIG_MANIFEST_FILENAMES};
 use super::{TempContent, TempPath, TempPathType};
+use crate::core::path::get_global_ignore_file_path;
 use crate::utils::{
-    errors::std_error,
-    path::{format_path_namespace, pathbuf_to_string, remove_dir_prefix, valid_directory_path},
+    errors::{invalid_data_error, std_error},
+    path::{
+        format_path_namespace, pathbuf_to_string, remove_dir_prefix, valid_directory_path,
+        valid_utf8_path,
+    },
 };
 use base64;
-use fs_tree::FsTreeBuilder;
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    WalkBuilder,
+};
 use serde_json::from_str;
 use std::{fs, io::Error, path::Path};
 
-pub fn mine_paths_from(directory_path: &str) -> Result<Vec<TempPath>, Error> {
+pub const TEMPLO_IGNORE_FILENAME: &str = ".temploignore";
+
+// The .temploignore files are always honored, the .gitignore and
+// .git/info/exclude files only when gitignore is true.
+pub fn mine_paths_from(directory_path: &str, gitignore: bool) -> Result<Vec<TempPath>, Error> {
     valid_directory_path(directory_path)?;
 
-    let paths_to_ignore = get_paths_to_ignore(directory_path)?;
+    let ignore_matcher = get_ignore_matcher(directory_path)?;
 
-    let fs_tree = (FsTreeBuilder::new(directory_path))
-        .ignore_paths(&paths_to_ignore[..])
-        .build();
+    let mut builder = WalkBuilder::new(directory_path);
+    builder
+        .standard_filters(false)
+        .git_ignore(gitignore)
+        .git_exclude(gitignore)
+        .parents(gitignore)
+        .require_git(false)
+        .add_custom_ignore_filename(TEMPLO_IGNORE_FILENAME)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(move |entry| {
+            let is_dir = matches!(entry.file_type(), Some(file_type) if file_type.is_dir());
+            entry.depth() == 0
+                || (entry.file_name() != ".git"
+                    && !ignore_matcher.matched(entry.path(), is_dir).is_ignore())
+        });
 
-    let vec_fs_tree: Vec<TempPath> = fs_tree
-        .into_iter()
-        .map(|path| TempPath::new(path.expect("Not is possible find the folder.")))
-        .collect();
+    let global_ignore_file = get_global_ignore_file_path()?;
+    if global_ignore_file.is_file() {
+        if let Some(err) = builder.add_ignore(global_ignore_file) {
+            return Err(invalid_data_error(&err.to_string()));
+        }
+    }
+
+    let walker = builder.build();
 
-    Ok(vec_fs_tree)
+    let mut paths = vec![];
+    for entry in walker {
+        let entry = std_error(entry)?;
+        valid_utf8_path(entry.path())?;
+        paths.push(TempPath::new(entry.into_path()));
+    }
+
+    Ok(paths)
 }
 
-pub fn mine_files_from_paths(paths: Vec<TempPath>, directory: &str) -> Vec<TempContent> {
+pub fn mine_files_from_paths(
+    paths: Vec<TempPath>,
+    directory: &str,
+) -> Result<Vec<TempContent>, Error> {
     paths
         .into_iter()
         .filter(|path| path.path_type == TempPathType::File)
         .map(|file| {
-            let file_path: TempPath = remove_dir_prefix(file.clone(), directory).unwrap();
+            let file_path: TempPath = remove_dir_prefix(file.clone(), directory)?;
             let filename = pathbuf_to_string(format_path_namespace(file_path.path));
-            let text = fs::read_to_string(file.path).expect("Error when read file content");
-            TempContent::new(filename, base64::encode(text))
+            // Files are kept as bytes, so non UTF-8 and binary files can be saved
+            let bytes = fs::read(file.path)?;
+            Ok(TempContent::new(filename, base64::encode(bytes)))
         })
         .collect()
 }
 
-fn get_paths_to_ignore(directory_path: &str) -> Result<Vec<String>, Error> {
-    let template_config = Path::new(directory_path).join("TemplateConfig");
-
-    if !template_config.exists() {
-        return Ok(vec!["".to_string()]);
-    }
+// The ignore file is a list of gitignore patterns, relative to the template directory
+fn get_ignore_matcher(directory_path: &str) -> Result<Gitignore, Error> {
+    let ignore_filename = Path::new(directory_path)
+        .join("TemplateConfig")
+        .join("ignore.json");
 
-    let ignore_filename = template_config.join("ignore.json");
-
-    let mut paths_to_ignore = if ignore_filename.exists() {
+    let manifest_patterns =
+        get_config_manifest(directory_path)?.and_then(|manifest| manifest.ignore);
+    let patterns: Vec<String> = if let Some(patterns) = manifest_patterns {
+        patterns
+    } else if ignore_filename.exists() {
         std_error(from_str(&fs::read_to_string(ignore_filename)?))?
     } else {
         vec![]
     };
-    paths_to_ignore.push("./TemplateConfig/".to_string());
 
-    let real_paths_to_ignore: Vec<String> = paths_to_ignore
-        .into_iter()
-        .map(|path| Path::new(directory_path).join(path))
-        .map(pathbuf_to_string)
-        .collect();
+    build_ignore_matcher(directory_path, &patterns)
+}
+
+// The TemplateConfig folder and the manifest are always ignored
+pub fn build_ignore_matcher(root: &str, patterns: &[String]) -> Result<Gitignore, Error> {
+    let mut builder = GitignoreBuilder::new(root);
+
+    for pattern in patterns.iter() {
+        std_error(builder.add_line(None, &get_gitignore_pattern(pattern)))?;
+    }
+    std_error(builder.add_line(None, "/TemplateConfig/"))?;
+
+    for filename in CONFIG_MANIFEST_FILENAMES.iter() {
+        std_error(builder.add_line(None, &format!("/{}", filename)))?;
+    }
 
-    Ok(real_paths_to_ignore)
+    std_error(builder.build())
+}
+
+// Old ignore files have paths like "./folder", which are anchored to the template directory
+fn get_gitignore_pattern(pattern: &str) -> String {
+    let (negation, path) = match pattern.strip_prefix('!') {
+        Some(path) => ("!", path),
+        None => ("", pattern),
+    };
+
+    match path.strip_prefix("./") {
+        Some(path) => format!("{}/{}", negation, path),
+        None => pattern.to_string(),
+    }
 }
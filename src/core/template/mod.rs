@@ -1,18 +1,41 @@
 pub mod generator;
+pub mod archive;
+pub mod attributes;
 pub mod maker;
 pub mod miner;
 pub mod engine;
+pub mod case;
 pub mod config;
+pub mod composer;
+pub mod differ;
+pub mod filter;
 pub mod getter;
+pub mod git;
+pub mod hooks;
+pub mod inheritance;
+pub mod injector;
+pub mod lint;
+pub mod manifest;
+pub mod upgrader;
+pub mod replacer;
+pub mod report;
+pub mod requirements;
+pub mod secrets;
+pub mod sink;
+pub mod size;
+pub mod snapshot;
+pub mod trust;
+pub mod undo;
 
 use super::http;
 
 #[cfg(test)]
 mod tests;
 
-use config::ConfigArg;
+use crate::utils::{errors::std_error, hash::sha256_hex};
+use config::{ConfigArg, ConfigAttribute, ConfigExtends, ConfigInjection, ConfigRequirement};
 use serde_derive::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{io::Error, path::PathBuf};
 use tabled::Tabled;
 
 // STRUCTS
@@ -25,7 +48,13 @@ pub struct Template {
     pub updated_at: Option<String>,
     pub paths: Vec<TempPath>,
     pub contents: Vec<TempContent>,
-    pub args: Option<Vec<ConfigArg>>
+    pub args: Option<Vec<ConfigArg>>,
+    pub hooks: Option<Vec<String>>,
+    pub requirements: Option<Vec<ConfigRequirement>>,
+    pub injections: Option<Vec<ConfigInjection>>,
+    pub extends: Option<ConfigExtends>,
+    pub attributes: Option<Vec<ConfigAttribute>>,
+    pub source: Option<TempSource>,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -54,6 +83,22 @@ pub enum TempPathType {
     Dir,
 }
 
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum TempSource {
+    Remote {
+        url: String,
+    },
+    // A .tpo file can be shared like a remote template, so it is not trusted either
+    File {
+        path: String,
+    },
+    Git {
+        repository: String,
+        rev: String,
+        commit: String,
+    },
+}
+
 // IMPLEMENTATIONS
 
 impl TempPath {
@@ -75,6 +120,30 @@ impl TempContent {
     }
 }
 
+impl TempSource {
+    // The local templates have no source and are the most trusted ones
+    fn trust_level(source: &Option<TempSource>) -> u8 {
+        match source {
+            None => 2,
+            Some(TempSource::Git { .. }) => 1,
+            Some(TempSource::Remote { .. }) | Some(TempSource::File { .. }) => 0,
+        }
+    }
+
+    // Source of a template made from other templates, so the hooks of a
+    // remote layer are never run as if they were local.
+    pub fn least_trusted(
+        source: Option<TempSource>,
+        other: Option<TempSource>,
+    ) -> Option<TempSource> {
+        if Self::trust_level(&other) < Self::trust_level(&source) {
+            other
+        } else {
+            source
+        }
+    }
+}
+
 impl Template {
     pub fn fmt(&self) -> TemplateDisplayInfo {
         TemplateDisplayInfo {
@@ -82,4 +151,9 @@ impl Template {
             created_at: self.created_at.clone(),
         }
     }
+
+    pub fn hash(&self) -> Result<String, Error> {
+        let template_string = std_error(serde_json::to_string(self))?;
+        Ok(sha256_hex(template_string.as_bytes()))
+    }
 }
@@ -0,0 +1,180 @@
+use super::attributes::get_file_attributes;
+use super::case::TextCase;
+use super::config::ConfigArg;
+use super::{TempContent, TempPath, Template};
+use crate::utils::errors::{invalid_input_error, std_error};
+use crate::utils::path::{pathbuf_to_string, str_to_pathbuf};
+use regex::{Captures, Regex};
+use std::{cmp::Reverse, collections::HashMap, io::Error};
+
+// Literal text of the project that is turned into an arg placeholder
+#[derive(Debug, PartialEq, Clone)]
+pub struct TempReplacement {
+    pub literal: String,
+    pub key: String,
+}
+
+impl TempReplacement {
+    // Syntax: literal=key
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let (literal, key) = match text.rsplit_once('=') {
+            Some((literal, key)) => (literal.to_string(), key.trim().to_string()),
+            None => {
+                return Err(invalid_input_error(&format!(
+                    "Invalid replacement \"{}\", use the literal=key syntax.",
+                    text
+                )))
+            }
+        };
+
+        let key_regex = Regex::new(r"^[\w-]+$").unwrap();
+        if literal.is_empty() || !key_regex.is_match(&key) {
+            return Err(invalid_input_error(&format!(
+                "Invalid replacement \"{}\", use the literal=key syntax.",
+                text
+            )));
+        }
+
+        Ok(Self { literal, key })
+    }
+}
+
+// A spelling of the literal and the case modifier that renders it back
+struct LiteralVariant {
+    text: String,
+    key: String,
+    case: Option<TextCase>,
+}
+
+// Replaces the literals and their case variants (MyApp, myApp, my_app, my-app and
+// MY_APP) in the paths and text contents with placeholders, declaring the args that
+// are not declared yet. The arg default is the literal, so the project is
+// generated back when the defaults are used.
+pub fn replace_literals(
+    template: Template,
+    replacements: &[TempReplacement],
+) -> Result<Template, Error> {
+    if replacements.is_empty() {
+        return Ok(template);
+    }
+
+    let variants = get_literal_variants(replacements);
+    let alternatives: Vec<String> = variants
+        .iter()
+        .map(|variant| regex::escape(&variant.text))
+        .collect();
+    let literal_regex = std_error(Regex::new(&alternatives.join("|")))?;
+
+    let content_variants: HashMap<&str, String> = variants
+        .iter()
+        .map(|variant| {
+            (
+                variant.text.as_str(),
+                get_placeholder(variant, "{> ", " <}"),
+            )
+        })
+        .collect();
+    let path_variants: HashMap<&str, String> = variants
+        .iter()
+        .map(|variant| {
+            (
+                variant.text.as_str(),
+                get_placeholder(variant, "([ ", " ])"),
+            )
+        })
+        .collect();
+
+    let replace_path = |path: &str| {
+        literal_regex
+            .replace_all(path, |caps: &Captures| path_variants[&caps[0]].clone())
+            .to_string()
+    };
+
+    let paths = template
+        .paths
+        .iter()
+        .map(|path| TempPath {
+            path: str_to_pathbuf(&replace_path(&pathbuf_to_string(path.path.clone()))),
+            path_type: path.path_type.clone(),
+        })
+        .collect();
+
+    let attributes = template.attributes.clone().unwrap_or_default();
+    let mut contents = vec![];
+
+    for content in template.contents.iter() {
+        let bytes = std_error(base64::decode(&content.text))?;
+        let is_binary = get_file_attributes(&attributes, &content.file_path)?.binary;
+
+        // Binary and non UTF-8 files are kept as they are
+        let text = match String::from_utf8(bytes) {
+            Ok(text) if !is_binary => base64::encode(
+                literal_regex
+                    .replace_all(&text, |caps: &Captures| content_variants[&caps[0]].clone())
+                    .as_bytes(),
+            ),
+            _ => content.text.clone(),
+        };
+
+        contents.push(TempContent::new(replace_path(&content.file_path), text));
+    }
+
+    let mut args = template.args.clone().unwrap_or_default();
+    for replacement in replacements.iter() {
+        if !args.iter().any(|arg| arg.key == replacement.key) {
+            args.push(ConfigArg {
+                key: replacement.key.clone(),
+                query: format!("{} ({}): ", replacement.key, replacement.literal),
+                about: None,
+                default: Some(replacement.literal.clone()),
+                secret: None,
+            });
+        }
+    }
+
+    Ok(Template {
+        paths,
+        contents,
+        args: Some(args),
+        ..template
+    })
+}
+
+// Longer variants come first, so the regex does not match a variant inside another one
+fn get_literal_variants(replacements: &[TempReplacement]) -> Vec<LiteralVariant> {
+    let mut variants: Vec<LiteralVariant> = vec![];
+
+    for replacement in replacements.iter() {
+        let mut literal_variants = vec![LiteralVariant {
+            text: replacement.literal.clone(),
+            key: replacement.key.clone(),
+            case: None,
+        }];
+
+        for case in TextCase::ALL.iter() {
+            literal_variants.push(LiteralVariant {
+                text: case.apply(&replacement.literal),
+                key: replacement.key.clone(),
+                case: Some(*case),
+            });
+        }
+
+        for variant in literal_variants {
+            if !variant.text.is_empty()
+                && !variants.iter().any(|current| current.text == variant.text)
+            {
+                variants.push(variant);
+            }
+        }
+    }
+
+    variants.sort_by_key(|variant| Reverse(variant.text.len()));
+    variants
+}
+
+fn get_placeholder(variant: &LiteralVariant, open: &str, close: &str) -> String {
+    match variant.case {
+        Some(case) => format!("{}{}|{}{}", open, variant.key, case.name(), close),
+        None => format!("{}{}{}", open, variant.key, close),
+    }
+}
@@ -0,0 +1,108 @@
+use super::engine::{get_public_args, TempEngineArg};
+use super::undo::{get_backup_path, GenRecord, GenRecordAction};
+use super::{TempPathType, Template};
+use crate::utils::errors::std_error;
+use serde_derive::Serialize;
+use serde_json::to_string_pretty;
+use std::{fs, io::Error, path::Path, time::Duration};
+
+// Structured result of a generation, for tools that consume the gen output
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GenReport {
+    pub template: GenReportTemplate,
+    pub args: Vec<TempEngineArg>,
+    pub created: Vec<GenReportPath>,
+    pub overwritten: Vec<String>,
+    pub skipped: Vec<String>,
+    pub duration_ms: u128,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GenReportTemplate {
+    pub namespace: String,
+    pub name: String,
+    pub hash: String,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct GenReportPath {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub path_type: String,
+    pub size: Option<u64>,
+}
+
+// Uses the generation record: the files that existed before the generation
+// and were left untouched are reported as skipped.
+pub fn make_report(
+    template_namespace: &str,
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+    record: &GenRecord,
+    record_path: &Path,
+    duration: Duration,
+) -> Result<GenReport, Error> {
+    let mut created = vec![];
+    let mut overwritten = vec![];
+    let mut skipped = vec![];
+
+    for entry in record.entries.iter() {
+        let file_path = directory.join(&entry.path);
+
+        // Removed after the generation, by a hook for example
+        if !file_path.exists() {
+            continue;
+        }
+
+        match entry.action {
+            GenRecordAction::Created => {
+                let (path_type, size) = match entry.path_type {
+                    TempPathType::File => ("file", Some(fs::metadata(&file_path)?.len())),
+                    TempPathType::Dir => ("dir", None),
+                };
+
+                created.push(GenReportPath {
+                    path: entry.path.clone(),
+                    path_type: path_type.to_string(),
+                    size,
+                });
+            }
+            GenRecordAction::Overwritten => {
+                let backup = fs::read(get_backup_path(record_path, &entry.path))?;
+
+                if fs::read(&file_path)? == backup {
+                    skipped.push(entry.path.clone());
+                } else {
+                    overwritten.push(entry.path.clone());
+                }
+            }
+        }
+    }
+
+    let args = match &template.args {
+        Some(config_args) => get_public_args(temp_args, config_args),
+        None => temp_args.to_vec(),
+    };
+
+    Ok(GenReport {
+        template: GenReportTemplate {
+            namespace: template_namespace.to_string(),
+            name: template.name.clone(),
+            hash: template.hash()?,
+            created_at: template.created_at.clone(),
+            updated_at: template.updated_at.clone(),
+        },
+        args,
+        created,
+        overwritten,
+        skipped,
+        duration_ms: duration.as_millis(),
+    })
+}
+
+pub fn report_to_json(report: &GenReport) -> Result<String, Error> {
+    std_error(to_string_pretty(report))
+}
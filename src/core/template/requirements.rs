@@ -0,0 +1,130 @@
+use super::config::ConfigRequirement;
+use crate::core::info::os_is_windows;
+use crate::utils::errors::not_found_error;
+use regex::Regex;
+use std::{
+    cmp::Ordering,
+    env,
+    io::Error,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// Verifies that every program required by the template is available,
+// reporting all the unsatisfied requirements at once.
+pub fn check_requirements(requirements: &[ConfigRequirement]) -> Result<(), Error> {
+    let unsatisfied: Vec<String> = requirements
+        .iter()
+        .filter_map(check_requirement)
+        .map(|msg| format!("    - {}", msg))
+        .collect();
+
+    if unsatisfied.is_empty() {
+        return Ok(());
+    }
+
+    Err(not_found_error(&format!(
+        "Template requirements are not satisfied:\n{}",
+        unsatisfied.join("\n")
+    )))
+}
+
+fn check_requirement(requirement: &ConfigRequirement) -> Option<String> {
+    // An invalid version would be compared as 0, so it would always be satisfied
+    let required_version = match &requirement.version {
+        Some(min_version) => match parse_required_version(min_version) {
+            Some(version) => Some(version),
+            None => {
+                return Some(format!(
+                    "\"{}\" has an invalid version requirement \"{}\", a version like \"1.56.0\" is expected.",
+                    requirement.name, min_version
+                ))
+            }
+        },
+        None => None,
+    };
+
+    let program = match find_program(&requirement.name) {
+        Some(program) => program,
+        None => return Some(format!("\"{}\" was not found on PATH.", requirement.name)),
+    };
+
+    let min_version = requirement.version.as_ref()?;
+    let required_version = required_version?;
+    let version_arg = requirement.version_arg.as_deref().unwrap_or("--version");
+
+    match get_program_version(&program, version_arg) {
+        Some(version) if compare_versions(&version, &required_version) != Ordering::Less => None,
+        Some(version) => Some(format!(
+            "\"{}\" {} is installed, but {} or newer is required.",
+            requirement.name, version, min_version
+        )),
+        None => Some(format!(
+            "Not is possible to get \"{}\" version, {} or newer is required.",
+            requirement.name, min_version
+        )),
+    }
+}
+
+fn find_program(name: &str) -> Option<PathBuf> {
+    if Path::new(name).components().count() > 1 {
+        return get_executable(Path::new(name));
+    }
+
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths).find_map(|dir| get_executable(&dir.join(name)))
+}
+
+fn get_executable(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    if !os_is_windows() {
+        return None;
+    }
+
+    let extensions = env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string());
+    extensions
+        .split(';')
+        .map(|ext| path.with_extension(ext.trim_start_matches('.')))
+        .find(|path| path.is_file())
+}
+
+fn get_program_version(program: &Path, version_arg: &str) -> Option<String> {
+    let output = Command::new(program).arg(version_arg).output().ok()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_version(&text)
+}
+
+// Gets the first version-like number of a text, e.g. "cargo 1.56.0 (4ed5d137b 2021-10-04)"
+pub fn parse_version(text: &str) -> Option<String> {
+    let regex = Regex::new(r"\d+(\.\d+)*").unwrap();
+    regex.find(text).map(|version| version.as_str().to_string())
+}
+
+// The required version must be only a version, e.g. "1.56" or "v14.17.0"
+pub fn parse_required_version(text: &str) -> Option<String> {
+    let regex = Regex::new(r"^v?(\d+(\.\d+)*)$").unwrap();
+    regex
+        .captures(text.trim())
+        .map(|captures| captures[1].to_string())
+}
+
+pub fn compare_versions(version: &str, other: &str) -> Ordering {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .split('.')
+            .map(|piece| piece.parse().unwrap_or(0))
+            .collect()
+    };
+    let (mut version, mut other) = (parse(version), parse(other));
+    let len = version.len().max(other.len());
+    version.resize(len, 0);
+    other.resize(len, 0);
+    version.cmp(&other)
+}
@@ -0,0 +1,248 @@
+use super::attributes::{get_file_attributes, matches_pattern};
+use super::config::{ConfigAllowedSecret, ConfigAttribute};
+use super::TempContent;
+use crate::utils::errors::std_error;
+use regex::Regex;
+use std::{collections::HashMap, io::Error, path::Path};
+
+// Known credential formats, by rule name
+const SECRET_RULES: [(&str, &str); 8] = [
+    (
+        "aws-access-key",
+        r"\b(?:AKIA|ASIA|AGPA|AIDA|AROA|AIPA|ANPA|ANVA)[A-Z0-9]{16}\b",
+    ),
+    (
+        "aws-secret-key",
+        r#"(?i)aws_?secret_?access_?key["']?\s*[:=]\s*["']?[A-Za-z0-9/+=]{40}"#,
+    ),
+    ("private-key", r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY-----"),
+    (
+        "github-token",
+        r"\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})",
+    ),
+    ("slack-token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}"),
+    ("stripe-key", r"\b[rs]k_live_[A-Za-z0-9]{20,}"),
+    ("google-api-key", r"\bAIza[A-Za-z0-9_-]{35}"),
+    (
+        "jwt",
+        r"\beyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}",
+    ),
+];
+
+const HIGH_ENTROPY_RULE: &str = "high-entropy";
+const DOTENV_RULE: &str = "dotenv-file";
+
+// Random tokens, like the base64 or base62 keys, have more than 4.3 bits of
+// entropy per char. Hex strings, like the commit hashes, have 4 at most.
+const MIN_TOKEN_LENGTH: usize = 24;
+const MIN_TOKEN_ENTROPY: f64 = 4.3;
+
+// Lock files are full of hashes of the dependencies
+const LOCK_FILENAMES: [&str; 7] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "composer.lock",
+    "poetry.lock",
+    "go.sum",
+];
+
+// The .env files with these suffixes have example values
+const DOTENV_EXAMPLE_SUFFIXES: [&str; 4] = [".example", ".sample", ".template", ".dist"];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SecretFinding {
+    pub file_path: String,
+    pub line: usize,
+    pub rule: String,
+    pub text: String,
+}
+
+impl SecretFinding {
+    // Only the start of the secret is shown, so the findings can be printed
+    pub fn masked_text(&self) -> String {
+        let visible: String = self.text.chars().take(4).collect();
+        format!("{}****", visible)
+    }
+
+    fn is_allowed(&self, allowed_secrets: &[ConfigAllowedSecret]) -> Result<bool, Error> {
+        for allowed in allowed_secrets.iter() {
+            let file_matches = match &allowed.file {
+                Some(pattern) => matches_pattern(pattern, &self.file_path)?,
+                None => true,
+            };
+            let rule_matches = match &allowed.rule {
+                Some(rule) => *rule == self.rule,
+                None => true,
+            };
+            let text_matches = match &allowed.text {
+                Some(text) => *text == self.text,
+                None => true,
+            };
+
+            if file_matches && rule_matches && text_matches {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+// Scans the text contents for credentials, skipping the binary and non UTF-8
+// files. The findings of the allowed secrets are not returned.
+pub fn scan_secrets(
+    contents: &[TempContent],
+    attributes: &[ConfigAttribute],
+    allowed_secrets: &[ConfigAllowedSecret],
+) -> Result<Vec<SecretFinding>, Error> {
+    let rules = SECRET_RULES
+        .iter()
+        .map(|(rule, pattern)| Ok((*rule, std_error(Regex::new(pattern))?)))
+        .collect::<Result<Vec<(&str, Regex)>, Error>>()?;
+    let token_regex = std_error(Regex::new(r"[A-Za-z0-9+/=_-]+"))?;
+
+    let mut findings = vec![];
+
+    for content in contents.iter() {
+        if get_file_attributes(attributes, &content.file_path)?.binary {
+            continue;
+        }
+
+        let bytes = std_error(base64::decode(&content.text))?;
+        let text = match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        let file_findings = if is_dotenv_file(&content.file_path) {
+            scan_dotenv_file(&content.file_path, &text)
+        } else {
+            scan_text(&content.file_path, &text, &rules, &token_regex)
+        };
+
+        for finding in file_findings {
+            if !finding.is_allowed(allowed_secrets)? {
+                findings.push(finding);
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+fn scan_text(
+    file_path: &str,
+    text: &str,
+    rules: &[(&str, Regex)],
+    token_regex: &Regex,
+) -> Vec<SecretFinding> {
+    let check_entropy = !LOCK_FILENAMES.contains(&get_filename(file_path));
+    let mut findings = vec![];
+
+    for (idx, line) in text.lines().enumerate() {
+        let mut line_findings: Vec<SecretFinding> = rules
+            .iter()
+            .flat_map(|(rule, regex)| {
+                regex.find_iter(line).map(move |found| SecretFinding {
+                    file_path: file_path.to_string(),
+                    line: idx + 1,
+                    rule: rule.to_string(),
+                    text: found.as_str().to_string(),
+                })
+            })
+            .collect();
+
+        // The tokens of a known credential are not reported again
+        if line_findings.is_empty() && check_entropy {
+            line_findings = token_regex
+                .find_iter(line)
+                .map(|found| found.as_str())
+                .filter(|token| is_high_entropy_token(token))
+                .map(|token| SecretFinding {
+                    file_path: file_path.to_string(),
+                    line: idx + 1,
+                    rule: HIGH_ENTROPY_RULE.to_string(),
+                    text: token.to_string(),
+                })
+                .collect();
+        }
+
+        findings.append(&mut line_findings);
+    }
+
+    findings
+}
+
+// Every value of a .env file is reported, they are usually real credentials
+fn scan_dotenv_file(file_path: &str, text: &str) -> Vec<SecretFinding> {
+    let mut findings = vec![];
+
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let value = match line.split_once('=') {
+            Some((_, value)) => value.trim().trim_matches(|ch| ch == '"' || ch == '\''),
+            None => continue,
+        };
+
+        // Arg placeholders are filled at generation time
+        if value.is_empty() || value.starts_with("{>") {
+            continue;
+        }
+
+        findings.push(SecretFinding {
+            file_path: file_path.to_string(),
+            line: idx + 1,
+            rule: DOTENV_RULE.to_string(),
+            text: value.to_string(),
+        });
+    }
+
+    findings
+}
+
+fn is_dotenv_file(file_path: &str) -> bool {
+    let filename = get_filename(file_path);
+
+    (filename == ".env" || filename.starts_with(".env."))
+        && !DOTENV_EXAMPLE_SUFFIXES
+            .iter()
+            .any(|suffix| filename.ends_with(suffix))
+}
+
+fn is_high_entropy_token(token: &str) -> bool {
+    token.len() >= MIN_TOKEN_LENGTH
+        && token.chars().any(|ch| ch.is_ascii_digit())
+        && token.chars().any(|ch| ch.is_ascii_alphabetic())
+        && get_shannon_entropy(token) >= MIN_TOKEN_ENTROPY
+}
+
+// Bits of information per char
+fn get_shannon_entropy(text: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for ch in text.chars() {
+        *counts.entry(ch).or_insert(0) += 1;
+    }
+
+    let length = text.chars().count() as f64;
+    counts
+        .values()
+        .map(|count| {
+            let probability = *count as f64 / length;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+fn get_filename(file_path: &str) -> &str {
+    Path::new(file_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(file_path)
+}
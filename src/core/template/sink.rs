@@ -0,0 +1,239 @@
+use crate::utils::errors::{invalid_input_error, std_error};
+use crate::utils::path::{format_path_namespace, pathbuf_to_string};
+use flate2::{write::GzEncoder, Compression};
+use rayon::prelude::*;
+use std::{
+    fs::{self, File},
+    io::{stdout, Error, Write},
+    path::{Path, PathBuf},
+};
+
+// Where the generated files and folders are written
+pub trait OutputSink {
+    fn create_dir(&mut self, path: &Path) -> Result<(), Error>;
+    fn write_file(&mut self, path: &Path, bytes: &[u8]) -> Result<(), Error>;
+    fn finish(&mut self) -> Result<(), Error>;
+
+    fn write_files(&mut self, files: Vec<(PathBuf, Vec<u8>)>) -> Result<(), Error> {
+        for (path, bytes) in files.iter() {
+            self.write_file(path, bytes)?;
+        }
+
+        Ok(())
+    }
+
+    // The generation log is hidden when quiet or when the output itself goes to stdout
+    fn logs(&self) -> bool {
+        true
+    }
+}
+
+pub struct FsSink {
+    directory: PathBuf,
+    logs: bool,
+}
+
+impl FsSink {
+    pub fn new(directory: &Path) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            logs: true,
+        }
+    }
+
+    pub fn quiet(self) -> Self {
+        Self {
+            logs: false,
+            ..self
+        }
+    }
+}
+
+impl OutputSink for FsSink {
+    fn create_dir(&mut self, path: &Path) -> Result<(), Error> {
+        // Generating into an existing project reuses its folders
+        fs::create_dir_all(self.directory.join(path))
+    }
+
+    fn write_file(&mut self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        fs::write(self.directory.join(path), bytes)
+    }
+
+    // The files are written concurrently, their folders already exist
+    fn write_files(&mut self, files: Vec<(PathBuf, Vec<u8>)>) -> Result<(), Error> {
+        files
+            .par_iter()
+            .map(|(path, bytes)| fs::write(self.directory.join(path), bytes))
+            .collect()
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn logs(&self) -> bool {
+        self.logs
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn from_filename(filename: &str) -> Result<Self, Error> {
+        if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
+            Ok(Self::TarGz)
+        } else if filename.ends_with(".zip") {
+            Ok(Self::Zip)
+        } else {
+            Err(invalid_input_error(
+                "The output archive must have .tar.gz or .zip extension.",
+            ))
+        }
+    }
+}
+
+// Entries are kept in memory, in generation order, until the sink is finished
+type SinkEntry = (String, Option<Vec<u8>>);
+
+fn upsert_entry(entries: &mut Vec<SinkEntry>, path: &Path, bytes: Option<Vec<u8>>) {
+    let path_name = pathbuf_to_string(format_path_namespace(path.to_path_buf()));
+
+    match entries.iter_mut().find(|(name, _)| *name == path_name) {
+        Some(entry) => entry.1 = bytes,
+        None => entries.push((path_name, bytes)),
+    }
+}
+
+pub struct ArchiveSink {
+    filename: PathBuf,
+    format: ArchiveFormat,
+    entries: Vec<SinkEntry>,
+}
+
+impl ArchiveSink {
+    pub fn new(filename: &Path) -> Result<Self, Error> {
+        let format = ArchiveFormat::from_filename(&pathbuf_to_string(filename.to_path_buf()))?;
+
+        Ok(Self {
+            filename: filename.to_path_buf(),
+            format,
+            entries: vec![],
+        })
+    }
+
+    fn write_tar_gz(&self, file: File) -> Result<(), Error> {
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+        for (path, bytes) in self.entries.iter() {
+            let mut header = tar::Header::new_gnu();
+
+            match bytes {
+                Some(bytes) => {
+                    header.set_entry_type(tar::EntryType::Regular);
+                    header.set_size(bytes.len() as u64);
+                    header.set_mode(0o644);
+                    header.set_cksum();
+                    builder.append_data(&mut header, path, bytes.as_slice())?;
+                }
+                None => {
+                    header.set_entry_type(tar::EntryType::Directory);
+                    header.set_size(0);
+                    header.set_mode(0o755);
+                    header.set_cksum();
+                    builder.append_data(&mut header, path, std::io::empty())?;
+                }
+            }
+        }
+
+        builder.into_inner()?.finish()?;
+        Ok(())
+    }
+
+    fn write_zip(&self, file: File) -> Result<(), Error> {
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default();
+
+        for (path, bytes) in self.entries.iter() {
+            match bytes {
+                Some(bytes) => {
+                    std_error(writer.start_file(path.as_str(), options))?;
+                    writer.write_all(bytes)?;
+                }
+                None => std_error(writer.add_directory(path.as_str(), options))?,
+            }
+        }
+
+        std_error(writer.finish())?;
+        Ok(())
+    }
+}
+
+impl OutputSink for ArchiveSink {
+    fn create_dir(&mut self, path: &Path) -> Result<(), Error> {
+        upsert_entry(&mut self.entries, path, None);
+        Ok(())
+    }
+
+    fn write_file(&mut self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        upsert_entry(&mut self.entries, path, Some(bytes.to_vec()));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        if let Some(parent) = self.filename.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let file = File::create(&self.filename)?;
+        match self.format {
+            ArchiveFormat::TarGz => self.write_tar_gz(file),
+            ArchiveFormat::Zip => self.write_zip(file),
+        }
+    }
+}
+
+// Writes the content of a single-file template to stdout
+#[derive(Default)]
+pub struct StdoutSink {
+    entries: Vec<SinkEntry>,
+}
+
+impl OutputSink for StdoutSink {
+    fn create_dir(&mut self, path: &Path) -> Result<(), Error> {
+        upsert_entry(&mut self.entries, path, None);
+        Ok(())
+    }
+
+    fn write_file(&mut self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        upsert_entry(&mut self.entries, path, Some(bytes.to_vec()));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        let files: Vec<&Vec<u8>> = self
+            .entries
+            .iter()
+            .filter_map(|(_, bytes)| bytes.as_ref())
+            .collect();
+
+        if files.len() != 1 {
+            return Err(invalid_input_error(
+                "Only single-file templates can be generated to stdout.",
+            ));
+        }
+
+        let mut stdout = stdout();
+        stdout.write_all(files[0])?;
+        stdout.flush()
+    }
+
+    fn logs(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,134 @@
+use super::TempContent;
+use crate::utils::errors::invalid_input_error;
+use std::{cmp::Reverse, io::Error};
+
+const KB: u64 = 1024;
+const MB: u64 = 1024 * KB;
+const GB: u64 = 1024 * MB;
+
+// Every file is stored as base64 inside the template file, so big files make
+// the template slow to read in every repository listing.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TempSizeLimits {
+    pub max_file_size: Option<u64>,
+    pub max_total_size: Option<u64>,
+}
+
+impl Default for TempSizeLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: Some(10 * MB),
+            max_total_size: Some(50 * MB),
+        }
+    }
+}
+
+impl TempSizeLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_file_size: None,
+            max_total_size: None,
+        }
+    }
+}
+
+// Size of the file bytes, without the base64 overhead
+pub fn get_content_size(content: &TempContent) -> u64 {
+    let text = content.text.trim_end();
+    let padding = text.chars().rev().take_while(|ch| *ch == '=').count() as u64;
+    (text.len() as u64 / 4 * 3).saturating_sub(padding)
+}
+
+pub fn get_total_size(contents: &[TempContent]) -> u64 {
+    contents.iter().map(get_content_size).sum()
+}
+
+// The files from the biggest to the smallest, with their sizes
+pub fn get_biggest_files(contents: &[TempContent], count: usize) -> Vec<(String, u64)> {
+    let mut files: Vec<(String, u64)> = contents
+        .iter()
+        .map(|content| (content.file_path.clone(), get_content_size(content)))
+        .collect();
+    files.sort_by_key(|(_, size)| Reverse(*size));
+    files.truncate(count);
+    files
+}
+
+pub fn check_size_limits(contents: &[TempContent], limits: &TempSizeLimits) -> Result<(), Error> {
+    let mut problems = vec![];
+
+    if let Some(max_file_size) = limits.max_file_size {
+        for (file_path, size) in get_biggest_files(contents, contents.len()) {
+            if size <= max_file_size {
+                break;
+            }
+
+            problems.push(format!(
+                "File \"{}\" has {}, the limit per file is {}.",
+                file_path,
+                format_size(size),
+                format_size(max_file_size)
+            ));
+        }
+    }
+
+    if let Some(max_total_size) = limits.max_total_size {
+        let total_size = get_total_size(contents);
+
+        if total_size > max_total_size {
+            problems.push(format!(
+                "The template files have {}, the limit is {}.",
+                format_size(total_size),
+                format_size(max_total_size)
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    problems.push("Ignore the big files or use --force to save them anyway.".to_string());
+    Err(invalid_input_error(&problems.join("\n")))
+}
+
+pub fn format_size(size: u64) -> String {
+    if size >= GB {
+        format!("{:.2} GB", size as f64 / GB as f64)
+    } else if size >= MB {
+        format!("{:.2} MB", size as f64 / MB as f64)
+    } else if size >= KB {
+        format!("{:.2} KB", size as f64 / KB as f64)
+    } else {
+        format!("{} B", size)
+    }
+}
+
+// Syntax: a number of bytes with an optional unit, like 500KB, 10MB or 1GB
+pub fn parse_size(text: &str) -> Result<u64, Error> {
+    let text = text.trim().to_uppercase();
+    let unit_start = text
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(unit_start);
+
+    let multiplier = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" => KB,
+        "M" | "MB" => MB,
+        "G" | "GB" => GB,
+        _ => return Err(invalid_size_error(&text)),
+    };
+
+    match number.parse::<f64>() {
+        Ok(number) if number >= 0.0 => Ok((number * multiplier as f64) as u64),
+        _ => Err(invalid_size_error(&text)),
+    }
+}
+
+fn invalid_size_error(text: &str) -> Error {
+    invalid_input_error(&format!(
+        "Invalid size \"{}\", use a size like 500KB, 10MB or 1GB.",
+        text
+    ))
+}
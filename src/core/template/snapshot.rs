@@ -0,0 +1,34 @@
+use super::Template;
+use crate::core::path::get_template_snapshots_path;
+use crate::utils::errors::{not_found_error, std_error};
+use serde_json::{from_str, to_string};
+use std::{fs, io::Error};
+
+// Keeps a copy of every generated template version, so a generated
+// project can later be compared against the version that produced it.
+pub fn save_snapshot(template: &Template) -> Result<(), Error> {
+    let snapshots_path = get_template_snapshots_path()?;
+    let snapshot_filename = snapshots_path.join(template.hash()?).with_extension("tpo");
+
+    if snapshot_filename.exists() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(&snapshots_path)?;
+    fs::write(snapshot_filename, std_error(to_string(template))?)
+}
+
+pub fn get_snapshot(template_hash: &str) -> Result<Template, Error> {
+    let snapshot_filename = get_template_snapshots_path()?
+        .join(template_hash)
+        .with_extension("tpo");
+
+    if !snapshot_filename.exists() {
+        return Err(not_found_error(&format!(
+            "Not is possible to find the template version \"{}\".",
+            template_hash
+        )));
+    }
+
+    std_error(from_str(&fs::read_to_string(snapshot_filename)?))
+}
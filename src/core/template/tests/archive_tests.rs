@@ -0,0 +1,122 @@
+use super::maker::make_template;
+use super::size::TempSizeLimits;
+use super::TestDir;
+use crate::utils::path::pathbuf_to_string;
+use flate2::{write::GzEncoder, Compression};
+use std::{fs, io::Write, path::PathBuf};
+
+const ENTRIES: &[(&str, &[u8])] = &[
+    ("kit/.gitignore", b"target/\n"),
+    (
+        "kit/TemplateConfig/args.json",
+        br#"[{"key": "name", "query": "Name: "}]"#,
+    ),
+    ("kit/TemplateConfig/ignore.json", br#"["*.log"]"#),
+    ("kit/debug.log", b"log"),
+    ("kit/empty.txt", b""),
+    ("kit/logo.png", &[0, 159, 146, 150]),
+    ("kit/src/main.rs", b"// {> name <}"),
+    ("kit/target/out", b"out"),
+];
+
+fn write_zip(filename: &PathBuf, entries: &[(&str, &[u8])]) {
+    let mut writer = zip::ZipWriter::new(fs::File::create(filename).unwrap());
+
+    for (path, bytes) in entries.iter() {
+        writer
+            .start_file(*path, zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(bytes).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+fn write_tar_gz(filename: &PathBuf, entries: &[(&str, &[u8])]) {
+    let file = fs::File::create(filename).unwrap();
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    for (path, bytes) in entries.iter() {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *bytes).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap();
+}
+
+fn assert_archive_template(filename: &PathBuf) {
+    let template = make_template(
+        "test".to_string(),
+        &pathbuf_to_string(filename.clone()),
+        None,
+        true,
+        &[],
+        &TempSizeLimits::default(),
+    )
+    .unwrap();
+
+    let paths: Vec<String> = template
+        .paths
+        .iter()
+        .map(|path| pathbuf_to_string(path.path.clone()))
+        .collect();
+    let contents: Vec<(String, Vec<u8>)> = template
+        .contents
+        .iter()
+        .map(|content| {
+            (
+                content.file_path.clone(),
+                base64::decode(&content.text).unwrap(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        paths,
+        vec![".gitignore", "empty.txt", "logo.png", "src", "src/main.rs"]
+    );
+    assert_eq!(
+        contents,
+        vec![
+            (".gitignore".to_string(), b"target/\n".to_vec()),
+            ("logo.png".to_string(), vec![0, 159, 146, 150]),
+            ("src/main.rs".to_string(), b"// {> name <}".to_vec()),
+        ]
+    );
+    assert_eq!(template.args.unwrap()[0].key, "name");
+}
+
+#[test]
+fn it_should_make_a_template_from_a_zip_archive() {
+    let directory = TestDir::new("zip-archive");
+    let filename = directory.join("kit.zip");
+    write_zip(&filename, ENTRIES);
+    assert_archive_template(&filename);
+}
+
+#[test]
+fn it_should_make_a_template_from_a_tar_gz_archive() {
+    let directory = TestDir::new("tar-archive");
+    let filename = directory.join("kit.tar.gz");
+    write_tar_gz(&filename, ENTRIES);
+    assert_archive_template(&filename);
+}
+
+#[test]
+fn it_should_reject_archive_entries_outside_of_the_root() {
+    let directory = TestDir::new("evil-archive");
+    let filename = directory.join("evil.zip");
+    write_zip(&filename, &[("../evil.rs", b"")]);
+
+    let template = make_template(
+        "test".to_string(),
+        &pathbuf_to_string(filename.clone()),
+        None,
+        true,
+        &[],
+        &TempSizeLimits::default(),
+    );
+
+    assert!(template.is_err());
+}
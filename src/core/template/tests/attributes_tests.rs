@@ -0,0 +1,139 @@
+use super::attributes::{convert_line_endings, encode_text, get_file_attributes};
+use super::config::{ConfigAttribute, LineEnding};
+use super::differ::diff_template;
+use super::engine::TempEngineArg;
+use super::generator::{gen_template, render_template};
+use super::make_test_template_with_files;
+use super::upgrader::{upgrade_template, UpgradeAction};
+use super::Template;
+use super::TestDir;
+use crate::utils::path::pathbuf_to_string;
+use std::fs;
+
+fn attribute(pattern: &str, eol: Option<LineEnding>, encoding: Option<&str>) -> ConfigAttribute {
+    ConfigAttribute {
+        pattern: pattern.to_string(),
+        eol,
+        encoding: encoding.map(str::to_string),
+        binary: None,
+    }
+}
+
+#[test]
+fn it_should_match_attributes_like_gitattributes() {
+    let attributes = vec![
+        attribute("*", Some(LineEnding::Lf), None),
+        attribute("*.bat", Some(LineEnding::Crlf), None),
+        attribute("/docs/*.txt", None, Some("latin1")),
+    ];
+
+    let script = get_file_attributes(&attributes, "scripts/run.bat").unwrap();
+    let nested_doc = get_file_attributes(&attributes, "src/docs/notes.txt").unwrap();
+    let doc = get_file_attributes(&attributes, "docs/notes.txt").unwrap();
+
+    assert_eq!(script.eol, LineEnding::Crlf);
+    assert_eq!(nested_doc.encoding, encoding_rs::UTF_8);
+    assert_eq!(doc.eol, LineEnding::Lf);
+    assert_eq!(doc.encoding, encoding_rs::WINDOWS_1252);
+    assert!(get_file_attributes(&[attribute("*", None, Some("klingon"))], "a.txt").is_err());
+}
+
+#[test]
+fn it_should_convert_line_endings() {
+    assert_eq!(convert_line_endings("a\r\nb\n", &LineEnding::Lf), "a\nb\n");
+    assert_eq!(
+        convert_line_endings("a\r\nb\n", &LineEnding::Crlf),
+        "a\r\nb\r\n"
+    );
+    assert_eq!(
+        convert_line_endings("a\r\nb\n", &LineEnding::Preserve),
+        "a\r\nb\n"
+    );
+}
+
+#[test]
+fn it_should_encode_text_in_the_file_encoding() {
+    let encode = |text: &str, label: &str| {
+        let attributes = [attribute("*", None, Some(label))];
+        encode_text(
+            text,
+            &get_file_attributes(&attributes, "a.txt").unwrap(),
+            "a.txt",
+        )
+    };
+
+    assert_eq!(encode("añ", "utf-16le").unwrap(), vec![0x61, 0, 0xf1, 0]);
+    assert_eq!(encode("añ", "utf-16be").unwrap(), vec![0, 0x61, 0, 0xf1]);
+    assert_eq!(encode("añ", "latin1").unwrap(), vec![0x61, 0xf1]);
+    assert!(encode("a→", "latin1").is_err());
+}
+
+#[test]
+fn it_should_apply_attributes_when_generating() {
+    let directory = TestDir::new("attributes");
+
+    let files: Vec<(&str, Vec<u8>)> = vec![
+        (
+            "main.rs",
+            b"fn main() {\r\n    // {> name <}\r\n}\r\n".to_vec(),
+        ),
+        // "café {> name <}" in latin1
+        ("notes.txt", b"caf\xe9 {> name <}\n".to_vec()),
+        ("logo.png", vec![0x89, 0x50, 0x4e, 0x47, 0xff, 0x0d, 0x0a]),
+    ];
+    let template = Template {
+        attributes: Some(vec![
+            attribute("*", Some(LineEnding::Lf), None),
+            attribute("*.txt", Some(LineEnding::Crlf), Some("latin1")),
+        ]),
+        ..make_test_template_with_files(&files)
+    };
+    let temp_args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "señor".to_string(),
+    }];
+
+    let render = render_template(&template, &temp_args).unwrap();
+    gen_template(&template, render, &directory, &temp_args, true).unwrap();
+    let main = fs::read(directory.join("main.rs")).unwrap();
+    let notes = fs::read(directory.join("notes.txt")).unwrap();
+    let logo = fs::read(directory.join("logo.png")).unwrap();
+
+    assert_eq!(main, b"fn main() {\n    // se\xc3\xb1or\n}\n".to_vec());
+    assert_eq!(notes, b"caf\xe9 se\xf1or\r\n".to_vec());
+    assert_eq!(logo, files[2].1);
+}
+
+#[test]
+fn it_should_apply_attributes_when_diffing_and_upgrading() {
+    let directory = TestDir::new("attributes-upgrade");
+
+    // The attributes match the template path, not the rendered one
+    let get_template = |text: &str| Template {
+        attributes: Some(vec![attribute("(*).bat", Some(LineEnding::Crlf), None)]),
+        ..make_test_template_with_files(&[("([ name ]).bat", text)])
+    };
+    let old_template = get_template("echo one\n");
+    let new_template = get_template("echo two\n");
+    let temp_args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "run".to_string(),
+    }];
+
+    let render = render_template(&old_template, &temp_args).unwrap();
+    gen_template(&old_template, render, &directory, &temp_args, true).unwrap();
+    let diffs = diff_template(
+        &old_template,
+        &temp_args,
+        &pathbuf_to_string(directory.to_path_buf()),
+    )
+    .unwrap();
+    let (_, changes) =
+        upgrade_template(&old_template, &new_template, &temp_args, &directory).unwrap();
+    let script = fs::read(directory.join("run.bat")).unwrap();
+
+    assert!(diffs.is_empty());
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].action, UpgradeAction::Updated);
+    assert_eq!(script, b"echo two\r\n".to_vec());
+}
@@ -0,0 +1,111 @@
+use super::attributes::get_file_attributes;
+use super::composer::{compose_templates, ConflictPolicy};
+use super::config::{ConfigArg, ConfigAttribute, LineEnding};
+use super::make_test_template_with_files;
+use super::trust::requires_trust;
+use super::{TempSource, Template};
+
+fn get_template(name: &str, files: &[(&str, &str)], arg_keys: &[&str]) -> Template {
+    Template {
+        name: name.to_string(),
+        args: Some(
+            arg_keys
+                .iter()
+                .map(|key| ConfigArg {
+                    key: key.to_string(),
+                    query: format!("{} of {}: ", key, name),
+                    about: None,
+                    default: None,
+                    secret: None,
+                })
+                .collect(),
+        ),
+        ..make_test_template_with_files(files)
+    }
+}
+
+fn get_layers() -> Vec<Template> {
+    vec![
+        get_template(
+            "service",
+            &[("main.rs", "service"), ("ci.yml", "service")],
+            &["name"],
+        ),
+        get_template("ci", &[("ci.yml", "ci")], &["name", "registry"]),
+    ]
+}
+
+#[test]
+fn it_should_compose_templates_in_order() {
+    let template = compose_templates(get_layers(), &ConflictPolicy::Override).unwrap();
+    let ci_content = template
+        .contents
+        .iter()
+        .find(|content| content.file_path == "ci.yml")
+        .unwrap();
+    let args = template.args.unwrap();
+
+    assert_eq!(template.name, "service+ci");
+    assert_eq!(template.paths.len(), 2);
+    assert_eq!(template.contents.len(), 2);
+    assert_eq!(ci_content.text, base64::encode("ci"));
+    assert_eq!(args.len(), 2);
+    assert_eq!(args[0].query, "name of service: ");
+}
+
+#[test]
+fn it_should_apply_the_conflict_policy() {
+    let template = compose_templates(get_layers(), &ConflictPolicy::Keep).unwrap();
+    let ci_content = template
+        .contents
+        .iter()
+        .find(|content| content.file_path == "ci.yml")
+        .unwrap();
+
+    assert_eq!(ci_content.text, base64::encode("service"));
+    assert!(compose_templates(get_layers(), &ConflictPolicy::Error).is_err());
+}
+
+#[test]
+fn it_should_require_trust_when_a_layer_is_remote() {
+    let remote = Some(TempSource::Remote {
+        url: "https://example.com/templates/ci".to_string(),
+    });
+    let mut layers = get_layers();
+    layers[1].hooks = Some(vec!["npm install".to_string()]);
+    layers[1].source = remote.clone();
+
+    let template = compose_templates(layers, &ConflictPolicy::Override).unwrap();
+
+    assert_eq!(template.source, remote);
+    assert!(requires_trust(&template));
+}
+
+#[test]
+fn it_should_merge_the_attributes_of_every_layer() {
+    let attribute = |pattern: &str, eol: LineEnding| ConfigAttribute {
+        pattern: pattern.to_string(),
+        eol: Some(eol),
+        encoding: None,
+        binary: None,
+    };
+    let mut layers = get_layers();
+    layers[0].attributes = Some(vec![
+        attribute("*.rs", LineEnding::Lf),
+        attribute("*.yml", LineEnding::Lf),
+    ]);
+    layers[1].attributes = Some(vec![attribute("*.yml", LineEnding::Crlf)]);
+
+    let template = compose_templates(layers, &ConflictPolicy::Override).unwrap();
+    let attributes = template.attributes.unwrap();
+
+    assert_eq!(attributes.len(), 3);
+    assert_eq!(
+        get_file_attributes(&attributes, "main.rs").unwrap().eol,
+        LineEnding::Lf
+    );
+    assert_eq!(
+        get_file_attributes(&attributes, "ci.yml").unwrap().eol,
+        LineEnding::Crlf
+    );
+}
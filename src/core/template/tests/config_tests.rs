@@ -1,8 +1,56 @@
-use super::config::{get_config_args, ConfigArg};
+use super::config::{get_config_file, parse_config_manifest, ConfigArg, ConfigManifest};
+use super::maker::make_template;
+use super::size::TempSizeLimits;
+use super::TestDir;
+use crate::utils::path::pathbuf_to_string;
+use std::fs;
+
+const TOML_MANIFEST: &str = r#"
+name = "my-template"
+description = "A template"
+ignore = ["*.log"]
+hooks = ["npm install"]
+
+[[args]]
+key = "port"
+query = "Port: "
+default = "8080"
+"#;
+
+const YAML_MANIFEST: &str = r#"
+name: my-template
+description: A template
+ignore:
+  - "*.log"
+hooks:
+  - npm install
+args:
+  - key: port
+    query: "Port: "
+    default: 8080
+"#;
+
+fn get_manifest() -> ConfigManifest {
+    ConfigManifest {
+        name: Some("my-template".to_string()),
+        description: Some("A template".to_string()),
+        args: Some(vec![ConfigArg {
+            key: "port".to_string(),
+            query: "Port: ".to_string(),
+            about: None,
+            default: Some("8080".to_string()),
+            secret: None,
+        }]),
+        ignore: Some(vec!["*.log".to_string()]),
+        hooks: Some(vec!["npm install".to_string()]),
+        ..ConfigManifest::default()
+    }
+}
 
 #[test]
 fn it_should_get_config_args_from_dir() {
-    let config_args = get_config_args("./folder-for-tests").unwrap();
+    let config_args: Option<Vec<ConfigArg>> =
+        get_config_file("./folder-for-tests", "args.json").unwrap();
 
     assert_eq!(
         config_args,
@@ -12,25 +60,102 @@ fn it_should_get_config_args_from_dir() {
                 query: "Your name (pultzlucas): ".to_string(),
                 about: None,
                 default: Some("pultzlucas".to_string()),
+                secret: None,
             },
             ConfigArg {
                 key: "lastName".to_string(),
                 query: "Your last name: ".to_string(),
                 about: None,
-                default: None
+                default: None,
+                secret: None,
             },
             ConfigArg {
                 key: "filename".to_string(),
                 query: "Javascript filename (script): ".to_string(),
                 about: Some("Sets javacript filename".to_string()),
-                default: Some("script".to_string())
+                default: Some("script".to_string()),
+                secret: None,
             },
             ConfigArg {
                 key: "folder1".to_string(),
                 query: "Folder name: ".to_string(),
                 about: Some("Sets folder1 name".to_string()),
-                default: Some("folder1".to_string())
+                default: Some("folder1".to_string()),
+                secret: None,
             }
         ])
     )
 }
+
+#[test]
+fn it_should_parse_toml_yaml_and_json_manifests() {
+    let json_manifest = r#"{"name": "my-template", "description": "A template",
+        "ignore": ["*.log"], "hooks": ["npm install"],
+        "args": [{"key": "port", "query": "Port: ", "default": "8080"}]}"#;
+
+    assert_eq!(
+        parse_config_manifest("templo.toml", TOML_MANIFEST).unwrap(),
+        get_manifest()
+    );
+    assert_eq!(
+        parse_config_manifest("templo.yaml", YAML_MANIFEST).unwrap(),
+        get_manifest()
+    );
+    assert_eq!(
+        parse_config_manifest("templo.json", json_manifest).unwrap(),
+        get_manifest()
+    );
+    assert!(parse_config_manifest("templo.toml", "unknown = true").is_err());
+}
+
+#[test]
+fn it_should_read_toml_numbers_as_strings() {
+    let toml_manifest = TOML_MANIFEST.replace(r#"default = "8080""#, "default = 8080");
+
+    assert_eq!(
+        parse_config_manifest("templo.toml", &toml_manifest).unwrap(),
+        get_manifest()
+    );
+}
+
+#[test]
+fn it_should_report_files_that_are_not_manifests() {
+    let package_config = r#"{"version": "1.0.0", "scripts": {"dev": "templo serve"}}"#;
+    let err = parse_config_manifest("templo.json", package_config).unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains("\"templo.json\" is not a valid template manifest"));
+}
+
+#[test]
+fn it_should_make_templates_with_the_manifest_settings() {
+    let directory = TestDir::new("manifest");
+    fs::create_dir_all(directory.join("TemplateConfig")).unwrap();
+    fs::write(directory.join("templo.toml"), TOML_MANIFEST).unwrap();
+    fs::write(directory.join("TemplateConfig/args.json"), "[]").unwrap();
+    fs::write(directory.join("debug.log"), "log").unwrap();
+    fs::write(directory.join("main.rs"), "{> port <}").unwrap();
+
+    let template = make_template(
+        "test".to_string(),
+        &pathbuf_to_string(directory.to_path_buf()),
+        None,
+        true,
+        &[],
+        &TempSizeLimits::default(),
+    )
+    .unwrap();
+
+    let manifest = get_manifest();
+    let paths: Vec<String> = template
+        .paths
+        .iter()
+        .map(|path| pathbuf_to_string(path.path.clone()))
+        .collect();
+
+    assert_eq!(paths, vec!["main.rs"]);
+    assert_eq!(template.description, manifest.description);
+    assert_eq!(template.args, manifest.args);
+    assert_eq!(template.hooks, manifest.hooks);
+}
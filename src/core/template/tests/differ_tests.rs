@@ -0,0 +1,79 @@
+use super::differ::{diff_template, DiffStatus};
+use super::TestDir;
+use super::{make_test_template, make_test_template_with_files};
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::{pathbuf_to_string, str_to_pathbuf};
+use std::fs;
+
+#[test]
+fn it_should_diff_template_render_with_directory() {
+    let directory = TestDir::new("diff");
+
+    let template = Template {
+        paths: ["same.txt", "changed.txt", "removed.txt"]
+            .iter()
+            .map(|path| TempPath {
+                path: str_to_pathbuf(path),
+                path_type: TempPathType::File,
+            })
+            .collect(),
+        contents: [
+            ("same.txt", "same\n"),
+            ("changed.txt", "before\n"),
+            ("removed.txt", "removed\n"),
+        ]
+        .iter()
+        .map(|(path, text)| TempContent::new(path.to_string(), base64::encode(text)))
+        .collect(),
+        ..make_test_template()
+    };
+
+    fs::write(directory.join("same.txt"), "same\n").unwrap();
+    fs::write(directory.join("changed.txt"), "after\n").unwrap();
+    fs::write(directory.join("added.txt"), "added\n").unwrap();
+
+    let diffs = diff_template(&template, &[], &pathbuf_to_string(directory.to_path_buf())).unwrap();
+
+    let statuses: Vec<(&str, DiffStatus)> = diffs
+        .iter()
+        .map(|diff| (diff.path.as_str(), diff.status.clone()))
+        .collect();
+
+    assert_eq!(
+        statuses,
+        vec![
+            ("changed.txt", DiffStatus::Changed),
+            ("removed.txt", DiffStatus::Removed),
+            ("added.txt", DiffStatus::Added),
+        ]
+    );
+    assert!(diffs[0].patch.contains("-before\n+after\n"));
+}
+
+#[test]
+fn it_should_diff_binary_files_by_bytes() {
+    let directory = TestDir::new("diff-binary");
+
+    let logo = vec![0x89, 0x50, 0x4e, 0x47, 0xff, 0x00];
+    let template = make_test_template_with_files(&[("logo.png", logo.clone())]);
+
+    fs::write(directory.join("logo.png"), &logo).unwrap();
+    let same = diff_template(&template, &[], &pathbuf_to_string(directory.to_path_buf())).unwrap();
+
+    fs::write(directory.join("logo.png"), [0x89, 0x50, 0x4e, 0x47, 0xfe]).unwrap();
+    fs::write(directory.join("icon.png"), [0x00, 0xff]).unwrap();
+    let diffs = diff_template(&template, &[], &pathbuf_to_string(directory.to_path_buf())).unwrap();
+
+    assert!(same.is_empty());
+    assert_eq!(diffs.len(), 2);
+    assert_eq!(diffs[0].status, DiffStatus::Changed);
+    assert_eq!(
+        diffs[0].patch,
+        "Binary files a/logo.png and b/logo.png differ\n"
+    );
+    assert_eq!(diffs[1].status, DiffStatus::Added);
+    assert_eq!(
+        diffs[1].patch,
+        "Binary files a/icon.png and b/icon.png differ\n"
+    );
+}
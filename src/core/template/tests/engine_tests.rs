@@ -12,18 +12,21 @@ fn get_content_parser_config_args() -> Vec<ConfigArg> {
             default: Some("value1".to_string()),
             about: Some("Sets key1".to_string()),
             key: "key1".to_string(),
+            secret: None,
         },
         ConfigArg {
             query: "asdasd".to_string(),
             default: None,
             about: None,
             key: "key2".to_string(),
+            secret: None,
         },
         ConfigArg {
             query: "asdasd".to_string(),
             default: Some("value3".to_string()),
             about: None,
             key: "key3".to_string(),
+            secret: None,
         },
     ]
 }
@@ -156,3 +159,19 @@ fn no_set_default_value_in_no_empty_engine_arg2() {
         }
     );
 }
+
+#[test]
+fn it_should_apply_the_key_case_modifiers() {
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "cool tool".to_string(),
+    }];
+    let text = "{> name|pascal <} {> name|camel <} {> name|snake <} {> name|kebab <} {> name|upper_snake <}";
+
+    let parsed = parse_content(text.to_string(), args.clone()).unwrap();
+    let parsed_path = parse_path("([ name|snake ]).rs".to_string(), args.clone()).unwrap();
+
+    assert_eq!(parsed, "CoolTool coolTool cool_tool cool-tool COOL_TOOL");
+    assert_eq!(parsed_path, "cool_tool.rs");
+    assert!(parse_content("{> name|upper <}".to_string(), args).is_err());
+}
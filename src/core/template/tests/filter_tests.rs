@@ -0,0 +1,95 @@
+use super::config::{ConfigArg, ConfigAttribute};
+use super::filter::filter_template;
+use super::make_test_template;
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::{pathbuf_to_string, str_to_pathbuf};
+
+fn get_template() -> Template {
+    let arg = |key: &str| ConfigArg {
+        key: key.to_string(),
+        query: format!("{}: ", key),
+        about: None,
+        default: None,
+        secret: None,
+    };
+
+    Template {
+        paths: vec![
+            ("ci", TempPathType::Dir),
+            ("ci/build.yml", TempPathType::File),
+            ("ci/([ registry ]).yml", TempPathType::File),
+            ("src", TempPathType::Dir),
+            ("src/main.rs", TempPathType::File),
+        ]
+        .into_iter()
+        .map(|(path, path_type)| TempPath {
+            path: str_to_pathbuf(path),
+            path_type,
+        })
+        .collect(),
+        contents: vec![
+            ("ci/build.yml", "image: {> image <}\n"),
+            ("src/main.rs", "// {> name <}\n"),
+        ]
+        .into_iter()
+        .map(|(path, text)| TempContent::new(path.to_string(), base64::encode(text)))
+        .collect(),
+        args: Some(vec![arg("name"), arg("image"), arg("registry")]),
+        ..make_test_template()
+    }
+}
+
+fn get_paths(template: &Template) -> Vec<String> {
+    template
+        .paths
+        .iter()
+        .map(|path| pathbuf_to_string(path.path.clone()))
+        .collect()
+}
+
+fn get_arg_keys(template: &Template) -> Vec<String> {
+    template
+        .args
+        .iter()
+        .flatten()
+        .map(|arg| arg.key.clone())
+        .collect()
+}
+
+#[test]
+fn it_should_generate_only_the_selected_paths() {
+    let template = filter_template(get_template(), Some("ci"), None).unwrap();
+
+    assert_eq!(
+        get_paths(&template),
+        vec!["ci", "ci/build.yml", "ci/([ registry ]).yml"]
+    );
+    assert_eq!(template.contents.len(), 1);
+    assert_eq!(get_arg_keys(&template), vec!["image", "registry"]);
+}
+
+#[test]
+fn it_should_exclude_paths_and_keep_their_parent_folders() {
+    let template = filter_template(get_template(), Some("**/*.yml"), Some("ci/build.yml")).unwrap();
+
+    assert_eq!(get_paths(&template), vec!["ci", "ci/([ registry ]).yml"]);
+    assert!(template.contents.is_empty());
+    assert_eq!(get_arg_keys(&template), vec!["registry"]);
+    assert!(filter_template(get_template(), Some("docs/*"), None).is_err());
+}
+
+#[test]
+fn it_should_ignore_the_keys_of_binary_files() {
+    let template = Template {
+        attributes: Some(vec![ConfigAttribute {
+            pattern: "build.yml".to_string(),
+            eol: None,
+            encoding: None,
+            binary: Some(true),
+        }]),
+        ..get_template()
+    };
+    let template = filter_template(template, Some("ci"), None).unwrap();
+
+    assert_eq!(get_arg_keys(&template), vec!["registry"]);
+}
@@ -0,0 +1,59 @@
+use super::engine::TempEngineArg;
+use super::generator::{gen_template, render_template};
+use super::make_test_template;
+use super::TestDir;
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::{pathbuf_to_string, str_to_pathbuf};
+use std::fs;
+
+fn get_large_template() -> Template {
+    let mut paths = vec![TempPath {
+        path: str_to_pathbuf("src"),
+        path_type: TempPathType::Dir,
+    }];
+    let mut contents = vec![];
+
+    for idx in 0..200 {
+        let file_path = format!("src/([ name ])_{}.rs", idx);
+        paths.push(TempPath {
+            path: str_to_pathbuf(&file_path),
+            path_type: TempPathType::File,
+        });
+        contents.push(TempContent::new(
+            file_path,
+            base64::encode(format!("// {{> name <}} {}\n", idx)),
+        ));
+    }
+
+    Template {
+        paths,
+        contents,
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_render_and_write_large_templates_in_order() {
+    let directory = TestDir::new("generator");
+    let temp_args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "app".to_string(),
+    }];
+
+    let template = get_large_template();
+    let render = render_template(&template, &temp_args).unwrap();
+    let rendered_paths = render.paths.clone();
+    let paths = gen_template(&template, render, &directory, &temp_args, true).unwrap();
+
+    let written: Vec<bool> = (0..200)
+        .map(|idx| {
+            let text = fs::read_to_string(directory.join(format!("src/app_{}.rs", idx))).unwrap();
+            text == format!("// app {}\n", idx)
+        })
+        .collect();
+
+    assert_eq!(rendered_paths, paths);
+    assert_eq!(pathbuf_to_string(paths[1].path.clone()), "src/app_0.rs");
+    assert_eq!(pathbuf_to_string(paths[200].path.clone()), "src/app_199.rs");
+    assert!(written.iter().all(|ok| *ok));
+}
@@ -0,0 +1,90 @@
+use super::maker::make_template_from_git;
+use super::size::TempSizeLimits;
+use super::TempSource;
+use super::TestDir;
+use crate::utils::path::pathbuf_to_string;
+use std::{fs, path::Path, process::Command};
+
+fn git(repo_path: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["-c", "user.name=templo", "-c", "user.email=templo@test"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+#[test]
+fn it_should_make_a_template_from_a_git_revision() {
+    let repo_path = TestDir::new("git");
+    fs::create_dir_all(repo_path.join("TemplateConfig")).unwrap();
+    fs::write(repo_path.join("main.rs"), "// v1").unwrap();
+    fs::write(
+        repo_path.join("TemplateConfig/ignore.json"),
+        "[\"notes.md\"]",
+    )
+    .unwrap();
+    fs::write(repo_path.join("notes.md"), "notes").unwrap();
+
+    git(&repo_path, &["init", "-q"]);
+    git(&repo_path, &["add", "-A"]);
+    git(&repo_path, &["commit", "-q", "-m", "v1"]);
+    git(&repo_path, &["tag", "v1"]);
+    let commit = git(&repo_path, &["rev-parse", "HEAD"]);
+
+    // Changes after the tag are not in the template
+    fs::write(repo_path.join("main.rs"), "// v2").unwrap();
+    fs::write(repo_path.join("untracked.rs"), "").unwrap();
+
+    let repo = pathbuf_to_string(repo_path.to_path_buf());
+    let limits = TempSizeLimits::default();
+    let template =
+        make_template_from_git("test".to_string(), &repo, "v1", None, &[], &limits).unwrap();
+    let missing_rev = make_template_from_git("test".to_string(), &repo, "v9", None, &[], &limits);
+
+    let paths: Vec<String> = template
+        .paths
+        .iter()
+        .map(|path| pathbuf_to_string(path.path.clone()))
+        .collect();
+
+    assert_eq!(paths, vec!["main.rs"]);
+    assert_eq!(template.contents[0].text, base64::encode("// v1"));
+    assert_eq!(
+        template.source,
+        Some(TempSource::Git {
+            repository: repo,
+            rev: "v1".to_string(),
+            commit,
+        })
+    );
+    assert!(missing_rev.is_err());
+}
+
+#[test]
+fn it_should_keep_the_tracked_files_ignored_by_temploignore() {
+    let repo_path = TestDir::new("git-ignore");
+    fs::create_dir_all(repo_path.join("docs")).unwrap();
+    fs::write(repo_path.join("docs/.temploignore"), "*.md").unwrap();
+    fs::write(repo_path.join("docs/guide.md"), "# Guide").unwrap();
+
+    git(&repo_path, &["init", "-q"]);
+    git(&repo_path, &["add", "-A"]);
+    git(&repo_path, &["commit", "-q", "-m", "docs"]);
+
+    let repo = pathbuf_to_string(repo_path.to_path_buf());
+    let limits = TempSizeLimits::default();
+    let template = make_template_from_git("test".to_string(), &repo, "HEAD", None, &[], &limits);
+
+    let paths: Vec<String> = template
+        .unwrap()
+        .paths
+        .iter()
+        .map(|path| pathbuf_to_string(path.path.clone()))
+        .collect();
+
+    assert_eq!(paths, vec!["docs", "docs/.temploignore", "docs/guide.md"]);
+}
@@ -0,0 +1,118 @@
+use super::config::get_config_file;
+use super::engine::TempEngineArg;
+use super::hooks::{get_hook_script, render_hooks, run_hooks};
+use super::TestDir;
+use std::fs;
+
+#[test]
+fn it_should_get_config_hooks_from_dir() {
+    let config_hooks: Option<Vec<String>> =
+        get_config_file("./folder-for-tests", "hooks.json").unwrap();
+    assert_eq!(config_hooks, Some(vec!["echo {> name <}".to_string()]));
+}
+
+#[test]
+fn it_should_render_hooks_with_engine_args() {
+    let hooks = vec![
+        "git init".to_string(),
+        "cargo new {> name <} --vcs none".to_string(),
+    ];
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "my-app".to_string(),
+    }];
+
+    assert_eq!(
+        render_hooks(&hooks, &args).unwrap(),
+        vec![
+            "git init".to_string(),
+            "cargo new my-app --vcs none".to_string()
+        ]
+    );
+}
+
+#[test]
+fn it_should_pass_hook_args_as_environment_variables() {
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "my app".to_string(),
+    }];
+    let script = get_hook_script("mkdir {> name <} && cd {> name|snake <}", &args).unwrap();
+
+    assert_eq!(
+        script.env,
+        vec![
+            ("TEMPLO_ARG_0".to_string(), "my app".to_string()),
+            ("TEMPLO_ARG_1".to_string(), "my_app".to_string()),
+        ]
+    );
+    assert!(!script.text.contains("my"));
+}
+
+#[test]
+fn it_should_not_run_commands_from_hook_args() {
+    let directory = TestDir::new("hooks");
+
+    let hooks = vec!["echo {> name <} > name.txt".to_string()];
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "x; echo injected > injected.txt".to_string(),
+    }];
+
+    let result = run_hooks(&hooks, &directory, &args, true);
+    let name = fs::read_to_string(directory.join("name.txt"));
+    let injected = directory.join("injected.txt").exists();
+
+    assert!(result.is_ok());
+    assert!(name.unwrap().contains("x; echo injected > injected.txt"));
+    assert!(!injected);
+}
+
+#[test]
+fn it_should_keep_hook_args_as_one_word_inside_quotes() {
+    let directory = TestDir::new("quoted-hooks");
+
+    let hooks = vec![
+        "printf '%s' 'Init {> name <}!' > single.txt".to_string(),
+        "printf '%s' \"Hello {> name <}!\" > double.txt".to_string(),
+        "printf '%s' {> name <} > bare.txt".to_string(),
+        "printf '%s' \\'{> name <} > escaped.txt".to_string(),
+    ];
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "my  *app's \"x\" `id`".to_string(),
+    }];
+
+    let result = run_hooks(&hooks, &directory, &args, true);
+    let read = |name: &str| fs::read_to_string(directory.join(name)).unwrap_or_default();
+    let (single, double, bare, escaped) = (
+        read("single.txt"),
+        read("double.txt"),
+        read("bare.txt"),
+        read("escaped.txt"),
+    );
+
+    assert!(result.is_ok());
+    assert_eq!(single, "Init my  *app's \"x\" `id`!");
+    assert_eq!(double, "Hello my  *app's \"x\" `id`!");
+    assert_eq!(bare, "my  *app's \"x\" `id`");
+    assert_eq!(escaped, "'my  *app's \"x\" `id`");
+}
+
+#[test]
+fn it_should_reference_hook_args_by_their_quoting() {
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "my-app".to_string(),
+    }];
+    let script = get_hook_script("echo {> name <} '{> name <}' \"{> name <}\"", &args).unwrap();
+
+    assert_eq!(
+        script.text,
+        "echo \"${TEMPLO_ARG_0}\" ''\"${TEMPLO_ARG_0}\"'' \"${TEMPLO_ARG_0}\""
+    );
+    assert_eq!(
+        script.env,
+        vec![("TEMPLO_ARG_0".to_string(), "my-app".to_string())]
+    );
+}
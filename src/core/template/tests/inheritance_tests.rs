@@ -0,0 +1,177 @@
+use super::attributes::get_file_attributes;
+use super::config::{ConfigArg, ConfigAttribute, ConfigExtends, LineEnding};
+use super::inheritance::resolve_template_with;
+use super::make_test_template_with_files;
+use super::trust::requires_trust;
+use super::{TempSource, Template};
+use crate::utils::errors::not_found_error;
+use std::io::Error;
+
+fn get_template(
+    name: &str,
+    files: &[(&str, &str)],
+    args: &[(&str, &str)],
+    extends: Option<ConfigExtends>,
+) -> Template {
+    Template {
+        name: name.to_string(),
+        args: Some(
+            args.iter()
+                .map(|(key, default)| ConfigArg {
+                    key: key.to_string(),
+                    query: format!("{}: ", key),
+                    about: None,
+                    default: Some(default.to_string()),
+                    secret: None,
+                })
+                .collect(),
+        ),
+        extends,
+        ..make_test_template_with_files(files)
+    }
+}
+
+fn attribute(pattern: &str, eol: LineEnding) -> ConfigAttribute {
+    ConfigAttribute {
+        pattern: pattern.to_string(),
+        eol: Some(eol),
+        encoding: None,
+        binary: None,
+    }
+}
+
+fn get_base(namespace: &str) -> Result<Template, Error> {
+    match namespace {
+        "main/base" => Ok(Template {
+            attributes: Some(vec![
+                attribute("*.rs", LineEnding::Lf),
+                attribute("*.md", LineEnding::Lf),
+            ]),
+            ..get_template(
+                "base",
+                &[
+                    ("main.rs", "base"),
+                    ("README.md", "base"),
+                    ("old.txt", "base"),
+                ],
+                &[("name", "app"), ("port", "80")],
+                None,
+            )
+        }),
+        "main/remote" => Ok(Template {
+            hooks: Some(vec!["npm install".to_string()]),
+            source: Some(TempSource::Remote {
+                url: "https://example.com/templates/remote".to_string(),
+            }),
+            ..get_template("remote", &[("main.rs", "remote")], &[], None)
+        }),
+        "main/loop" => Ok(get_template(
+            "loop",
+            &[],
+            &[],
+            Some(ConfigExtends {
+                template: "main/loop".to_string(),
+                delete: None,
+            }),
+        )),
+        _ => Err(not_found_error("Template not found.")),
+    }
+}
+
+#[test]
+fn it_should_inherit_the_base_template() {
+    let child = get_template(
+        "child",
+        &[("main.rs", "child"), ("new.txt", "child")],
+        &[("port", "8080")],
+        Some(ConfigExtends {
+            template: "main/base".to_string(),
+            delete: Some(vec!["old.txt".to_string()]),
+        }),
+    );
+
+    let template = resolve_template_with(child, &get_base).unwrap();
+    let mut files: Vec<(String, String)> = template
+        .contents
+        .iter()
+        .map(|content| (content.file_path.clone(), content.text.clone()))
+        .collect();
+    files.sort();
+    let args: Vec<(String, Option<String>)> = template
+        .args
+        .unwrap()
+        .into_iter()
+        .map(|arg| (arg.key, arg.default))
+        .collect();
+
+    assert_eq!(template.name, "child");
+    assert_eq!(template.extends, None);
+    assert_eq!(template.paths.len(), 3);
+    assert_eq!(
+        files,
+        vec![
+            ("README.md".to_string(), base64::encode("base")),
+            ("main.rs".to_string(), base64::encode("child")),
+            ("new.txt".to_string(), base64::encode("child")),
+        ]
+    );
+    assert_eq!(
+        args,
+        vec![
+            ("name".to_string(), Some("app".to_string())),
+            ("port".to_string(), Some("8080".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn it_should_override_the_base_attributes() {
+    let child = Template {
+        attributes: Some(vec![attribute("*.md", LineEnding::Crlf)]),
+        ..get_template(
+            "child",
+            &[("main.rs", "child")],
+            &[],
+            Some(ConfigExtends {
+                template: "main/base".to_string(),
+                delete: None,
+            }),
+        )
+    };
+
+    let template = resolve_template_with(child, &get_base).unwrap();
+    let attributes = template.attributes.unwrap();
+
+    assert_eq!(
+        get_file_attributes(&attributes, "main.rs").unwrap().eol,
+        LineEnding::Lf
+    );
+    assert_eq!(
+        get_file_attributes(&attributes, "README.md").unwrap().eol,
+        LineEnding::Crlf
+    );
+}
+
+#[test]
+fn it_should_fail_on_circular_inheritance() {
+    let template = get_base("main/loop").unwrap();
+    assert!(resolve_template_with(template, &get_base).is_err());
+}
+
+#[test]
+fn it_should_require_trust_when_the_base_is_remote() {
+    let child = get_template(
+        "child",
+        &[("main.rs", "child")],
+        &[],
+        Some(ConfigExtends {
+            template: "main/remote".to_string(),
+            delete: None,
+        }),
+    );
+
+    let template = resolve_template_with(child, &get_base).unwrap();
+
+    assert!(matches!(template.source, Some(TempSource::Remote { .. })));
+    assert!(requires_trust(&template));
+}
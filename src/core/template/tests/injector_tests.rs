@@ -0,0 +1,111 @@
+use super::config::{ConfigInjection, InjectionPosition};
+use super::engine::TempEngineArg;
+use super::generator::{gen_template, render_template};
+use super::injector::insert_snippet;
+use super::make_test_template_with_files;
+use super::Template;
+use super::TestDir;
+use std::fs;
+
+const MOD_FILE: &str = "mod cli;\n// templo: modules\n\nfn main() {}\n";
+
+fn get_injection(marker: &str, position: Option<InjectionPosition>, regex: bool) -> ConfigInjection {
+    ConfigInjection {
+        file: "src/main.rs".to_string(),
+        snippet: "mod {> name <};".to_string(),
+        marker: marker.to_string(),
+        position,
+        regex: Some(regex),
+    }
+}
+
+#[test]
+fn it_should_insert_snippet_after_marker() {
+    let injection = get_injection("// templo: modules", None, false);
+    let injected = insert_snippet(MOD_FILE, "mod core;", &injection).unwrap();
+
+    assert_eq!(
+        injected,
+        Some("mod cli;\n// templo: modules\nmod core;\n\nfn main() {}\n".to_string())
+    );
+}
+
+#[test]
+fn it_should_insert_snippet_before_regex_anchor_with_its_indentation() {
+    let text = "let routes = vec![\n    home,\n];\n";
+    let injection = get_injection(r"^\s*\];", Some(InjectionPosition::Before), true);
+    let injected = insert_snippet(text, "users,", &injection).unwrap();
+
+    assert_eq!(
+        injected,
+        Some("let routes = vec![\n    home,\nusers,\n];\n".to_string())
+    );
+
+    let injection = get_injection(r"^\s*home,", Some(InjectionPosition::After), true);
+    let injected = insert_snippet(text, "users,", &injection).unwrap();
+
+    assert_eq!(
+        injected,
+        Some("let routes = vec![\n    home,\n    users,\n];\n".to_string())
+    );
+}
+
+#[test]
+fn it_should_not_insert_snippet_twice() {
+    let injection = get_injection("// templo: modules", None, false);
+    let injected = insert_snippet(MOD_FILE, "mod core;", &injection)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(insert_snippet(&injected, "mod core;", &injection).unwrap(), None);
+}
+
+#[test]
+fn it_should_fail_when_marker_is_not_found() {
+    let injection = get_injection("// missing marker", None, false);
+    assert!(insert_snippet(MOD_FILE, "mod core;", &injection).is_err());
+}
+
+#[test]
+fn it_should_check_injections_before_writing_the_template() {
+    let directory = TestDir::new("inject");
+    fs::create_dir_all(directory.join("src")).unwrap();
+    fs::write(directory.join("src/main.rs"), MOD_FILE).unwrap();
+
+    let get_template = |injections: Vec<ConfigInjection>| Template {
+        injections: Some(injections),
+        ..make_test_template_with_files(&[("src/core.rs", "pub fn run() {}\n")])
+    };
+    let valid = get_injection("// templo: modules", None, false);
+    let missing_marker = get_injection("// templo: routes", None, false);
+    let outside = ConfigInjection {
+        file: "src/../../main.rs".to_string(),
+        ..valid.clone()
+    };
+    // Without the parent folders, this would be the existing src/main.rs
+    let escaping = ConfigInjection {
+        file: "../../src/main.rs".to_string(),
+        ..valid.clone()
+    };
+    let temp_args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "core".to_string(),
+    }];
+
+    let generate = |template: Template| {
+        let render = render_template(&template, &temp_args)?;
+        gen_template(&template, render, &directory, &temp_args, true)
+    };
+
+    let marker_result = generate(get_template(vec![valid.clone(), missing_marker]));
+    let outside_result = generate(get_template(vec![valid.clone(), outside]));
+    let escaping_result = generate(get_template(vec![escaping]));
+    let main = fs::read_to_string(directory.join("src/main.rs")).unwrap();
+    let core_exists = directory.join("src/core.rs").exists();
+
+    assert!(marker_result.is_err());
+    assert!(outside_result.is_err());
+    assert!(escaping_result.is_err());
+    assert_eq!(main, MOD_FILE);
+    assert!(!core_exists);
+}
@@ -0,0 +1,144 @@
+use super::config::ConfigArg;
+use super::lint::{lint_directory, lint_template, LintIssue, LintLevel};
+use super::make_test_template;
+use super::TestDir;
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::{pathbuf_to_string, str_to_pathbuf};
+use std::fs;
+
+fn make_arg(key: &str, default: Option<&str>) -> ConfigArg {
+    ConfigArg {
+        key: key.to_string(),
+        query: format!("{}: ", key),
+        about: None,
+        default: default.map(str::to_string),
+        secret: None,
+    }
+}
+
+fn get_template() -> Template {
+    Template {
+        paths: vec![
+            TempPath {
+                path: str_to_pathbuf("([name]).rs"),
+                path_type: TempPathType::File,
+            },
+            TempPath {
+                path: str_to_pathbuf("App.rs"),
+                path_type: TempPathType::File,
+            },
+            TempPath {
+                path: str_to_pathbuf("([folder])"),
+                path_type: TempPathType::Dir,
+            },
+        ],
+        contents: vec![TempContent::new(
+            "([name]).rs".to_string(),
+            base64::encode("// {> name <} {> version <}"),
+        )],
+        args: Some(vec![
+            make_arg("name", Some("app")),
+            make_arg("unused", None),
+            make_arg("folder", Some("bad/folder")),
+        ]),
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_report_template_issues() {
+    let issues = lint_template(&get_template()).unwrap();
+
+    assert_eq!(
+        issues,
+        vec![
+            LintIssue {
+                level: LintLevel::Error,
+                message:
+                    "Key \"version\" is used in \"([name]).rs\" but is not declared as an arg."
+                        .to_string()
+            },
+            LintIssue {
+                level: LintLevel::Warning,
+                message: "Arg \"unused\" is declared but is not used.".to_string()
+            },
+            LintIssue {
+                level: LintLevel::Error,
+                message: "Default value \"bad/folder\" of arg \"folder\" is not valid in a path."
+                    .to_string()
+            },
+            LintIssue {
+                level: LintLevel::Error,
+                message:
+                    "Paths \"([name]).rs\" and \"App.rs\" collide when rendered as \"App.rs\"."
+                        .to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_should_report_paths_that_cannot_be_rendered() {
+    let template = Template {
+        paths: vec![
+            TempPath {
+                path: str_to_pathbuf("([name|shout]).rs"),
+                path_type: TempPathType::File,
+            },
+            TempPath {
+                path: str_to_pathbuf("([name|pascal]).rs"),
+                path_type: TempPathType::File,
+            },
+        ],
+        args: Some(vec![make_arg("name", Some("app"))]),
+        ..make_test_template()
+    };
+
+    let issues = lint_template(&template).unwrap();
+
+    assert_eq!(
+        issues,
+        vec![LintIssue {
+            level: LintLevel::Error,
+            message: "Path \"([name|shout]).rs\" cannot be rendered: Invalid case \"shout\", use pascal, camel, snake, kebab or upper_snake."
+                .to_string()
+        }]
+    );
+}
+
+#[test]
+fn it_should_report_where_a_key_is_used() {
+    let template = Template {
+        hooks: Some(vec!["npm install {> package <}".to_string()]),
+        ..make_test_template()
+    };
+
+    assert_eq!(
+        lint_template(&template).unwrap(),
+        vec![LintIssue {
+            level: LintLevel::Error,
+            message: "Key \"package\" is used in the hook \"npm install {> package <}\" but is not declared as an arg.".to_string()
+        }]
+    );
+}
+
+#[test]
+fn it_should_report_malformed_config_files_with_line_numbers() {
+    let directory = TestDir::new("lint");
+    fs::create_dir_all(directory.join("TemplateConfig")).unwrap();
+    fs::write(directory.join("main.rs"), "{> name <}").unwrap();
+    fs::write(
+        directory.join("TemplateConfig/args.json"),
+        "[\n    {\n        \"key\": \"name\",\n    }\n]",
+    )
+    .unwrap();
+
+    let issues = lint_directory(&pathbuf_to_string(directory.to_path_buf())).unwrap();
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].level, LintLevel::Error);
+    assert!(issues[0]
+        .message
+        .starts_with("TemplateConfig/args.json is malformed:"));
+    assert!(issues[0].message.contains("line 4"));
+}
@@ -1,6 +1,7 @@
 use crate::{core::template::{TempPath, maker::make_template}, utils::date::get_date_now_string};
 
 use super::{TempContent, TempPathType, Template, config::ConfigArg};
+use super::size::TempSizeLimits;
 use crate::utils::path::str_to_pathbuf;
 
 const FOLDER: &'static str = "./folder-for-tests";
@@ -62,27 +63,37 @@ fn get_correct_template_struct() -> Template {
                 ConfigArg { 
                     key: "name".to_string(), query: "Your name (pultzlucas): ".to_string(), 
                     about: None, 
-                    default: Some("pultzlucas".to_string()) 
+                    default: Some("pultzlucas".to_string()),
+                    secret: None,
                 }, 
                 ConfigArg { 
                     key: "lastName".to_string(), 
                     query: "Your last name: ".to_string(), 
                     about: None, 
-                    default: None 
+                    default: None,
+                    secret: None,
                 }, 
                 ConfigArg { 
                     key: "filename".to_string(), 
                     query: "Javascript filename (script): ".to_string(), 
                     about: Some("Sets javacript filename".to_string()), 
-                    default: Some("script".to_string()) 
+                    default: Some("script".to_string()),
+                    secret: None,
                 }, 
                 ConfigArg { 
                     key: "folder1".to_string(), 
                     query: "Folder name: ".to_string(), 
                     about: Some("Sets folder1 name".to_string()), 
-                    default: Some("folder1".to_string()) 
+                    default: Some("folder1".to_string()),
+                    secret: None,
                 }
-            ]) 
+            ]),
+            hooks: Some(vec!["echo {> name <}".to_string()]),
+            requirements: None,
+            injections: None,
+            extends: None,
+            attributes: None,
+            source: None,
         }
 }
 
@@ -90,7 +101,7 @@ fn get_correct_template_struct() -> Template {
 #[ignore]
 fn test_template_creation() {
     let template = 
-        make_template("test".to_string(), FOLDER, None).unwrap();
+        make_template("test".to_string(), FOLDER, None, true, &[], &TempSizeLimits::default()).unwrap();
     
     assert_eq!(template, get_correct_template_struct());
 }
@@ -0,0 +1,70 @@
+use super::config::ConfigArg;
+use super::engine::TempEngineArg;
+use super::make_test_template;
+use super::manifest::make_manifest;
+use crate::utils::hash::sha256_hex;
+use crate::utils::path::str_to_pathbuf;
+use std::{fs, path::Path};
+
+use super::{TempPath, TempPathType, Template};
+
+fn get_template() -> Template {
+    Template {
+        updated_at: Some("2021-10-15 00:00:00 UTC".to_string()),
+        args: Some(vec![
+            ConfigArg {
+                key: "name".to_string(),
+                query: "Name: ".to_string(),
+                about: None,
+                default: None,
+                secret: None,
+            },
+            ConfigArg {
+                key: "token".to_string(),
+                query: "Token: ".to_string(),
+                about: None,
+                default: None,
+                secret: Some(true),
+            },
+        ]),
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_make_manifest_without_secret_args() {
+    let directory = Path::new("./folder-for-tests");
+    let args = vec![
+        TempEngineArg {
+            key: "name".to_string(),
+            value: "my-app".to_string(),
+        },
+        TempEngineArg {
+            key: "token".to_string(),
+            value: "s3cr3t".to_string(),
+        },
+    ];
+    let generated_paths = vec![
+        TempPath {
+            path: str_to_pathbuf("tree"),
+            path_type: TempPathType::Dir,
+        },
+        TempPath {
+            path: str_to_pathbuf("rust-file.rs"),
+            path_type: TempPathType::File,
+        },
+    ];
+
+    let manifest =
+        make_manifest("main/test", &get_template(), &args, directory, &generated_paths).unwrap();
+
+    assert_eq!(manifest.template, "main/test");
+    assert_eq!(manifest.updated_at, Some("2021-10-15 00:00:00 UTC".to_string()));
+    assert_eq!(manifest.args, vec![args[0].clone()]);
+    assert_eq!(manifest.files.len(), 1);
+    assert_eq!(manifest.files[0].path, "rust-file.rs");
+    assert_eq!(
+        manifest.files[0].hash,
+        sha256_hex(&fs::read(directory.join("rust-file.rs")).unwrap())
+    );
+}
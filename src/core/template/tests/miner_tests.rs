@@ -0,0 +1,185 @@
+use super::maker::make_template_data;
+use super::miner::mine_paths_from;
+use super::TestDir;
+use super::{TempPath, TempPathType};
+use crate::utils::path::{normalize_path, pathbuf_to_string, remove_dir_prefix, str_to_pathbuf};
+use std::{fs, io::ErrorKind, path::Path};
+
+fn make_files(directory: &Path, files: &[&str]) {
+    for file in files.iter() {
+        let file_path = directory.join(file);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(file_path, "").unwrap();
+    }
+}
+
+fn get_mined_paths(directory: &str, gitignore: bool) -> Vec<String> {
+    mine_paths_from(directory, gitignore)
+        .unwrap()
+        .into_iter()
+        .map(|path| pathbuf_to_string(remove_dir_prefix(path, directory).unwrap().path))
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+#[test]
+fn it_should_ignore_paths_with_gitignore_patterns() {
+    let directory = TestDir::new("miner");
+    make_files(
+        &directory,
+        &[
+            "app.log",
+            "keep.log",
+            "docs/readme.md",
+            "src/main.rs",
+            "src/debug.log",
+            "src/target/build",
+            "target/build",
+        ],
+    );
+    fs::create_dir_all(directory.join("TemplateConfig")).unwrap();
+    fs::write(
+        directory.join("TemplateConfig/ignore.json"),
+        r#"["**/*.log", "!keep.log", "target/", "./docs"]"#,
+    )
+    .unwrap();
+
+    let paths = get_mined_paths(&pathbuf_to_string(directory.to_path_buf()), true);
+
+    assert_eq!(paths, vec!["keep.log", "src", "src/main.rs"]);
+}
+
+#[test]
+fn it_should_honor_gitignore_files_unless_disabled() {
+    let directory = TestDir::new("miner-git");
+    make_files(
+        &directory,
+        &[
+            ".env",
+            ".git/config",
+            "notes.md",
+            "src/main.rs",
+            "sub/a.tmp",
+            "sub/b.rs",
+            "target/build",
+        ],
+    );
+    fs::write(directory.join(".gitignore"), "target/\n.env\n").unwrap();
+    fs::write(directory.join(".temploignore"), "notes.md\n").unwrap();
+    fs::write(directory.join("sub/.gitignore"), "*.tmp\n").unwrap();
+
+    let directory_path = pathbuf_to_string(directory.to_path_buf());
+    let paths = get_mined_paths(&directory_path, true);
+    let all_paths = get_mined_paths(&directory_path, false);
+
+    assert_eq!(
+        paths,
+        vec![
+            ".gitignore",
+            ".temploignore",
+            "src",
+            "src/main.rs",
+            "sub",
+            "sub/.gitignore",
+            "sub/b.rs"
+        ]
+    );
+    assert_eq!(
+        all_paths,
+        vec![
+            ".env",
+            ".gitignore",
+            ".temploignore",
+            "src",
+            "src/main.rs",
+            "sub",
+            "sub/.gitignore",
+            "sub/a.tmp",
+            "sub/b.rs",
+            "target",
+            "target/build"
+        ]
+    );
+}
+
+#[test]
+fn it_should_mine_the_same_paths_with_any_directory_syntax() {
+    let directory = TestDir::new("miner-path");
+    make_files(&directory, &["src/main.rs", "readme.md"]);
+
+    let dir_path = pathbuf_to_string(directory.to_path_buf());
+    let get_paths = |dir_path: &str| -> Vec<String> {
+        make_template_data(dir_path, true)
+            .unwrap()
+            .paths
+            .into_iter()
+            .map(|path| pathbuf_to_string(path.path))
+            .collect()
+    };
+    let paths = get_paths(&dir_path);
+    let trailing_slash_paths = get_paths(&format!("{}/", dir_path));
+    let parent_dir_paths = get_paths(&format!("{}/src/..", dir_path));
+
+    assert_eq!(paths, vec!["readme.md", "src", "src/main.rs"]);
+    assert_eq!(trailing_slash_paths, paths);
+    assert_eq!(parent_dir_paths, paths);
+}
+
+#[test]
+fn it_should_return_errors_for_invalid_directories() {
+    let directory = TestDir::new("miner-error");
+    make_files(&directory, &["file.txt"]);
+
+    let dir_path = pathbuf_to_string(directory.to_path_buf());
+    let missing_dir = mine_paths_from(&format!("{}/missing", dir_path), true);
+    let file_dir = mine_paths_from(&format!("{}/file.txt", dir_path), true);
+    let empty_dir = mine_paths_from("", true);
+
+    assert_eq!(missing_dir.unwrap_err().kind(), ErrorKind::NotFound);
+    assert_eq!(file_dir.unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(empty_dir.unwrap_err().kind(), ErrorKind::InvalidInput);
+}
+
+#[cfg(unix)]
+#[test]
+fn it_should_return_an_error_for_non_utf8_paths() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let directory = TestDir::new("miner-utf8");
+    fs::write(directory.join(OsStr::from_bytes(b"file\xff.txt")), "").unwrap();
+
+    let result = make_template_data(&pathbuf_to_string(directory.to_path_buf()), true);
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn it_should_remove_the_directory_prefix_however_it_is_written() {
+    let get_relative_path = |path: &str, directory: &str| {
+        let path = TempPath {
+            path: str_to_pathbuf(path),
+            path_type: TempPathType::File,
+        };
+        remove_dir_prefix(path, directory).map(|path| pathbuf_to_string(path.path))
+    };
+
+    for directory in ["dir", "./dir", "dir/", "./dir/"] {
+        assert_eq!(get_relative_path("./dir/a.rs", directory).unwrap(), "a.rs");
+        assert_eq!(
+            get_relative_path("dir/src/a.rs", directory).unwrap(),
+            "src/a.rs"
+        );
+    }
+    assert!(get_relative_path("other/a.rs", "dir").is_err());
+}
+
+#[test]
+fn it_should_keep_the_parent_folders_that_leave_the_path() {
+    let normalize = |path: &str| pathbuf_to_string(normalize_path(Path::new(path)));
+
+    assert_eq!(normalize("./dir/src/../a.rs"), "dir/a.rs");
+    assert_eq!(normalize("../../x"), "../../x");
+    assert_eq!(normalize("a/../../x"), "../x");
+    assert_eq!(normalize("/.."), "/");
+    assert_eq!(normalize("/a/../../x"), "/x");
+}
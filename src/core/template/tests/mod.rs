@@ -1,5 +1,108 @@
+mod archive_tests;
+mod attributes_tests;
+mod composer_tests;
 mod config_tests;
+mod differ_tests;
 mod engine_tests;
+mod filter_tests;
+mod generator_tests;
+mod git_tests;
+mod hooks_tests;
+mod inheritance_tests;
+mod injector_tests;
 mod maker_tests;
+mod lint_tests;
+mod manifest_tests;
+mod miner_tests;
+mod replacer_tests;
+mod report_tests;
+mod requirements_tests;
+mod secrets_tests;
+mod sink_tests;
+mod size_tests;
+mod template_tests;
+mod trust_tests;
+mod undo_tests;
+mod upgrader_tests;
 
 pub use super::*;
+
+use crate::utils::path::str_to_pathbuf;
+use std::{
+    env, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process,
+};
+
+// Template fixture of the tests, they override only the fields they check
+pub fn make_test_template() -> Template {
+    Template {
+        name: "test".to_string(),
+        description: None,
+        created_at: "2021-10-14 00:00:00 UTC".to_string(),
+        updated_at: None,
+        paths: vec![],
+        contents: vec![],
+        args: None,
+        hooks: None,
+        requirements: None,
+        injections: None,
+        extends: None,
+        attributes: None,
+        source: None,
+    }
+}
+
+// Test template with a file path and content for each file
+pub fn make_test_template_with_files<T: AsRef<[u8]>>(files: &[(&str, T)]) -> Template {
+    Template {
+        paths: files
+            .iter()
+            .map(|(path, _)| TempPath {
+                path: str_to_pathbuf(path),
+                path_type: TempPathType::File,
+            })
+            .collect(),
+        contents: files
+            .iter()
+            .map(|(path, bytes)| TempContent::new(path.to_string(), base64::encode(bytes)))
+            .collect(),
+        ..make_test_template()
+    }
+}
+
+// Empty temporary folder of a test, removed when dropped, so it is removed
+// even when the test fails
+pub struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("templo-{}-test-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TestDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,80 @@
+use super::config::ConfigArg;
+use super::engine::{parse_content, parse_path, TempEngineArg};
+use super::make_test_template;
+use super::replacer::{replace_literals, TempReplacement};
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::{pathbuf_to_string, str_to_pathbuf};
+
+const CONTENT: &str =
+    "mod my_app;\nstruct MyApp;\nconst MY_APP: &str = \"my-app\";\nlet myApp = 1;\n";
+
+fn get_template() -> Template {
+    Template {
+        paths: vec![
+            TempPath {
+                path: str_to_pathbuf("my-app"),
+                path_type: TempPathType::Dir,
+            },
+            TempPath {
+                path: str_to_pathbuf("my-app/MyApp.rs"),
+                path_type: TempPathType::File,
+            },
+        ],
+        contents: vec![TempContent::new(
+            "my-app/MyApp.rs".to_string(),
+            base64::encode(CONTENT),
+        )],
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_replace_literals_and_case_variants_with_placeholders() {
+    let replacements = vec![TempReplacement::parse("my-app=name").unwrap()];
+    let template = replace_literals(get_template(), &replacements).unwrap();
+
+    let paths: Vec<String> = template
+        .paths
+        .iter()
+        .map(|path| pathbuf_to_string(path.path.clone()))
+        .collect();
+    let text = String::from_utf8(base64::decode(&template.contents[0].text).unwrap()).unwrap();
+
+    assert_eq!(paths, vec!["([ name ])", "([ name ])/([ name|pascal ]).rs"]);
+    assert_eq!(
+        template.contents[0].file_path,
+        "([ name ])/([ name|pascal ]).rs"
+    );
+    assert_eq!(
+        text,
+        "mod {> name|snake <};\nstruct {> name|pascal <};\nconst {> name|upper_snake <}: &str = \"{> name <}\";\nlet {> name|camel <} = 1;\n"
+    );
+    assert_eq!(
+        template.args,
+        Some(vec![ConfigArg {
+            key: "name".to_string(),
+            query: "name (my-app): ".to_string(),
+            about: None,
+            default: Some("my-app".to_string()),
+            secret: None,
+        }])
+    );
+
+    // Generating with the default value gives the original project back
+    let args = vec![TempEngineArg {
+        key: "name".to_string(),
+        value: "my-app".to_string(),
+    }];
+    assert_eq!(parse_content(text, args.clone()).unwrap(), CONTENT);
+    assert_eq!(
+        parse_path(template.contents[0].file_path.clone(), args).unwrap(),
+        "my-app/MyApp.rs"
+    );
+}
+
+#[test]
+fn it_should_reject_invalid_replacements() {
+    assert!(TempReplacement::parse("my-app").is_err());
+    assert!(TempReplacement::parse("=name").is_err());
+    assert!(TempReplacement::parse("my-app=na me").is_err());
+}
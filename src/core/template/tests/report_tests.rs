@@ -0,0 +1,84 @@
+use super::config::ConfigArg;
+use super::engine::TempEngineArg;
+use super::generator::{gen_template, render_template};
+use super::make_test_template_with_files;
+use super::report::{make_report, GenReportPath};
+use super::undo::{record_generation, save_record};
+use super::Template;
+use super::TestDir;
+use std::{fs, time::Duration};
+
+#[test]
+fn it_should_report_a_generation() {
+    let root = TestDir::new("report");
+    let directory = root.join("project");
+    let record_path = root.join("record");
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("README.md"), "# My project\n").unwrap();
+    fs::write(directory.join("LICENSE"), "MIT\n").unwrap();
+
+    let files = [
+        ("main.rs", "fn main() {}\n"),
+        ("README.md", "# {> name <}\n"),
+        ("LICENSE", "MIT\n"),
+    ];
+    let template = Template {
+        args: Some(vec![
+            ConfigArg {
+                key: "name".to_string(),
+                query: "Name: ".to_string(),
+                about: None,
+                default: None,
+                secret: None,
+            },
+            ConfigArg {
+                key: "token".to_string(),
+                query: "Token: ".to_string(),
+                about: None,
+                default: None,
+                secret: Some(true),
+            },
+        ]),
+        ..make_test_template_with_files(&files)
+    };
+    let temp_args = vec![
+        TempEngineArg {
+            key: "name".to_string(),
+            value: "app".to_string(),
+        },
+        TempEngineArg {
+            key: "token".to_string(),
+            value: "secret".to_string(),
+        },
+    ];
+
+    let render = render_template(&template, &temp_args).unwrap();
+    let record =
+        record_generation(&template, &render, &temp_args, &directory, &record_path).unwrap();
+    gen_template(&template, render, &directory, &temp_args, true).unwrap();
+    let record = save_record(record, &directory, &record_path).unwrap();
+    let report = make_report(
+        "main/test",
+        &template,
+        &temp_args,
+        &directory,
+        &record,
+        &record_path,
+        Duration::from_millis(42),
+    )
+    .unwrap();
+
+    assert_eq!(report.template.namespace, "main/test");
+    assert_eq!(report.args, temp_args[..1].to_vec());
+    assert_eq!(
+        report.created,
+        vec![GenReportPath {
+            path: "main.rs".to_string(),
+            path_type: "file".to_string(),
+            size: Some(13),
+        }]
+    );
+    assert_eq!(report.overwritten, vec!["README.md"]);
+    assert_eq!(report.skipped, vec!["LICENSE"]);
+    assert_eq!(report.duration_ms, 42);
+}
@@ -0,0 +1,65 @@
+use super::config::ConfigRequirement;
+use super::requirements::{
+    check_requirements, compare_versions, parse_required_version, parse_version,
+};
+use std::cmp::Ordering;
+
+#[test]
+fn it_should_parse_version_from_program_output() {
+    assert_eq!(
+        parse_version("cargo 1.56.0 (4ed5d137b 2021-10-04)"),
+        Some("1.56.0".to_string())
+    );
+    assert_eq!(parse_version("v14.17.0"), Some("14.17.0".to_string()));
+    assert_eq!(parse_version("no version here"), None);
+}
+
+#[test]
+fn it_should_parse_only_plain_required_versions() {
+    assert_eq!(parse_required_version("1.56"), Some("1.56".to_string()));
+    assert_eq!(
+        parse_required_version("v14.17.0"),
+        Some("14.17.0".to_string())
+    );
+    assert_eq!(parse_required_version("latest"), None);
+    assert_eq!(parse_required_version(">=1.2"), None);
+}
+
+#[test]
+fn it_should_compare_versions_numerically() {
+    assert_eq!(compare_versions("1.10.0", "1.9"), Ordering::Greater);
+    assert_eq!(compare_versions("1.56", "1.56.0"), Ordering::Equal);
+    assert_eq!(compare_versions("0.9.1", "1.0"), Ordering::Less);
+}
+
+#[test]
+fn it_should_list_every_missing_requirement() {
+    let requirements = vec![
+        ConfigRequirement {
+            name: "templo-missing-tool-1".to_string(),
+            version: None,
+            version_arg: None,
+        },
+        ConfigRequirement {
+            name: "templo-missing-tool-2".to_string(),
+            version: Some("1.0".to_string()),
+            version_arg: None,
+        },
+    ];
+
+    let err = check_requirements(&requirements).unwrap_err().to_string();
+    assert!(err.contains("\"templo-missing-tool-1\" was not found on PATH."));
+    assert!(err.contains("\"templo-missing-tool-2\" was not found on PATH."));
+}
+
+#[test]
+fn it_should_report_invalid_version_requirements() {
+    let requirements = vec![ConfigRequirement {
+        name: "templo-missing-tool".to_string(),
+        version: Some("latest".to_string()),
+        version_arg: None,
+    }];
+
+    let err = check_requirements(&requirements).unwrap_err().to_string();
+    assert!(err.contains("\"templo-missing-tool\" has an invalid version requirement \"latest\""));
+}
@@ -0,0 +1,95 @@
+use super::generator::write_template;
+use super::make_test_template;
+use super::sink::{ArchiveSink, FsSink};
+use super::TestDir;
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::str_to_pathbuf;
+use flate2::read::GzDecoder;
+use std::io::Read;
+use std::{fs, fs::File};
+
+fn get_template() -> Template {
+    Template {
+        paths: vec![
+            ("src", TempPathType::Dir),
+            ("src/main.rs", TempPathType::File),
+            ("README.md", TempPathType::File),
+        ]
+        .into_iter()
+        .map(|(path, path_type)| TempPath {
+            path: str_to_pathbuf(path),
+            path_type,
+        })
+        .collect(),
+        contents: vec![TempContent::new(
+            "src/main.rs".to_string(),
+            base64::encode("fn main() {}\n"),
+        )],
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_generate_a_template_into_archives() {
+    let directory = TestDir::new("sink");
+    let tar_filename = directory.join("starter.tar.gz");
+    let zip_filename = directory.join("starter.zip");
+
+    write_template(
+        &get_template(),
+        &[],
+        &mut ArchiveSink::new(&tar_filename).unwrap(),
+    )
+    .unwrap();
+    write_template(
+        &get_template(),
+        &[],
+        &mut ArchiveSink::new(&zip_filename).unwrap(),
+    )
+    .unwrap();
+
+    let mut tar_entries = vec![];
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(&tar_filename).unwrap()));
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let mut text = String::new();
+        entry.read_to_string(&mut text).unwrap();
+        tar_entries.push((entry.path().unwrap().display().to_string(), text));
+    }
+
+    let mut zip = zip::ZipArchive::new(File::open(&zip_filename).unwrap()).unwrap();
+    let mut zip_text = String::new();
+    zip.by_name("src/main.rs")
+        .unwrap()
+        .read_to_string(&mut zip_text)
+        .unwrap();
+    let zip_len = zip.len();
+
+    assert_eq!(
+        tar_entries,
+        vec![
+            ("src".to_string(), String::new()),
+            ("src/main.rs".to_string(), "fn main() {}\n".to_string()),
+            ("README.md".to_string(), String::new()),
+        ]
+    );
+    assert_eq!(zip_len, 3);
+    assert_eq!(zip_text, "fn main() {}\n");
+    assert!(ArchiveSink::new(&directory.join("starter.rar")).is_err());
+}
+
+#[test]
+fn it_should_generate_into_an_existing_tree() {
+    let directory = TestDir::new("sink-existing");
+    fs::create_dir_all(directory.join("src")).unwrap();
+    fs::write(directory.join("src/lib.rs"), "pub mod app;\n").unwrap();
+
+    let mut sink = FsSink::new(&directory).quiet();
+    let result = write_template(&get_template(), &[], &mut sink);
+    let main = fs::read_to_string(directory.join("src/main.rs"));
+    let lib = fs::read_to_string(directory.join("src/lib.rs"));
+
+    assert!(result.is_ok());
+    assert_eq!(main.unwrap(), "fn main() {}\n");
+    assert_eq!(lib.unwrap(), "pub mod app;\n");
+}
@@ -0,0 +1,86 @@
+use super::maker::make_template;
+use super::size::{
+    check_size_limits, get_biggest_files, get_content_size, parse_size, TempSizeLimits,
+};
+use super::TempContent;
+use super::TestDir;
+use crate::utils::path::pathbuf_to_string;
+use std::{fs, io::ErrorKind};
+
+fn make_content(file_path: &str, size: usize) -> TempContent {
+    TempContent::new(file_path.to_string(), base64::encode(vec![0u8; size]))
+}
+
+#[test]
+fn it_should_get_the_size_of_the_decoded_contents() {
+    for size in [0, 1, 2, 3, 1000, 1001] {
+        assert_eq!(get_content_size(&make_content("file", size)), size as u64);
+    }
+}
+
+#[test]
+fn it_should_parse_sizes_with_units() {
+    assert_eq!(parse_size("512").unwrap(), 512);
+    assert_eq!(parse_size("2KB").unwrap(), 2048);
+    assert_eq!(parse_size("1.5mb").unwrap(), 1572864);
+    assert_eq!(parse_size("1 GB").unwrap(), 1073741824);
+    assert!(parse_size("ten").is_err());
+    assert!(parse_size("10TB").is_err());
+}
+
+#[test]
+fn it_should_check_the_file_and_total_size_limits() {
+    let contents = vec![
+        make_content("small.txt", 10),
+        make_content("data/big.csv", 3000),
+        make_content("medium.txt", 500),
+    ];
+    let file_limit = TempSizeLimits {
+        max_file_size: Some(1000),
+        max_total_size: None,
+    };
+    let total_limit = TempSizeLimits {
+        max_file_size: None,
+        max_total_size: Some(3000),
+    };
+
+    let file_error = check_size_limits(&contents, &file_limit).unwrap_err();
+
+    assert_eq!(file_error.kind(), ErrorKind::InvalidInput);
+    assert!(file_error.to_string().contains("data/big.csv"));
+    assert!(!file_error.to_string().contains("medium.txt"));
+    assert!(check_size_limits(&contents, &total_limit).is_err());
+    assert!(check_size_limits(&contents, &TempSizeLimits::unlimited()).is_ok());
+    assert_eq!(
+        get_biggest_files(&contents, 2),
+        vec![
+            ("data/big.csv".to_string(), 3000),
+            ("medium.txt".to_string(), 500)
+        ]
+    );
+}
+
+#[test]
+fn it_should_not_make_templates_bigger_than_the_limits() {
+    let directory = TestDir::new("size");
+    fs::write(directory.join("dataset.bin"), vec![1u8; 4096]).unwrap();
+
+    let make = |limits: TempSizeLimits| {
+        make_template(
+            "test".to_string(),
+            &pathbuf_to_string(directory.to_path_buf()),
+            None,
+            true,
+            &[],
+            &limits,
+        )
+    };
+    let limited = make(TempSizeLimits {
+        max_file_size: Some(1024),
+        max_total_size: None,
+    });
+    let forced = make(TempSizeLimits::unlimited());
+
+    assert!(limited.is_err());
+    assert_eq!(forced.unwrap().contents.len(), 1);
+}
@@ -0,0 +1,17 @@
+use super::make_test_template;
+use super::Template;
+
+#[test]
+fn it_should_change_template_hash_when_hooks_change() {
+    let template = Template {
+        hooks: Some(vec!["npm install".to_string()]),
+        ..make_test_template()
+    };
+    let changed = Template {
+        hooks: Some(vec!["rm -rf ~".to_string()]),
+        ..make_test_template()
+    };
+
+    assert_eq!(template.hash().unwrap(), template.clone().hash().unwrap());
+    assert_ne!(template.hash().unwrap(), changed.hash().unwrap());
+}
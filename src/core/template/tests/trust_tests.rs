@@ -0,0 +1,35 @@
+use super::make_test_template;
+use super::trust::requires_trust;
+use super::{TempSource, Template};
+
+fn get_template(hooks: Option<Vec<String>>, source: Option<TempSource>) -> Template {
+    Template {
+        hooks,
+        source,
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_require_trust_only_for_remote_templates_with_hooks() {
+    let remote = Some(TempSource::Remote {
+        url: "https://example.com/templates/test".to_string(),
+    });
+    let hooks = Some(vec!["npm install".to_string()]);
+
+    assert!(requires_trust(&get_template(hooks.clone(), remote.clone())));
+    assert!(!requires_trust(&get_template(hooks, None)));
+    assert!(!requires_trust(&get_template(None, remote.clone())));
+    assert!(!requires_trust(&get_template(Some(vec![]), remote)));
+}
+
+#[test]
+fn it_should_require_trust_for_template_files_with_hooks() {
+    let file = Some(TempSource::File {
+        path: "./shared/test.tpo".to_string(),
+    });
+    let hooks = Some(vec!["npm install".to_string()]);
+
+    assert!(requires_trust(&get_template(hooks, file.clone())));
+    assert!(!requires_trust(&get_template(None, file)));
+}
@@ -0,0 +1,99 @@
+use super::generator::{gen_template, render_template};
+use super::make_test_template;
+use super::undo::{
+    get_directory_hash, read_record, record_generation, save_record, undo_generation,
+};
+use super::TestDir;
+use super::{TempContent, TempPath, TempPathType, Template};
+use crate::utils::path::str_to_pathbuf;
+use std::{fs, path::Path};
+
+fn get_template() -> Template {
+    Template {
+        paths: vec![
+            ("src", TempPathType::Dir),
+            ("src/main.rs", TempPathType::File),
+            ("README.md", TempPathType::File),
+        ]
+        .into_iter()
+        .map(|(path, path_type)| TempPath {
+            path: str_to_pathbuf(path),
+            path_type,
+        })
+        .collect(),
+        contents: vec![
+            ("src/main.rs", "fn main() {}\n"),
+            ("README.md", "# Template\n"),
+        ]
+        .into_iter()
+        .map(|(path, text)| TempContent::new(path.to_string(), base64::encode(text)))
+        .collect(),
+        ..make_test_template()
+    }
+}
+
+#[test]
+fn it_should_undo_a_generation() {
+    let root = TestDir::new("undo");
+    let directory = root.join("project");
+    let record_path = root.join("record");
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("README.md"), "# My project\n").unwrap();
+
+    let template = get_template();
+    let render = render_template(&template, &[]).unwrap();
+    let record = record_generation(&template, &render, &[], &directory, &record_path).unwrap();
+    gen_template(&template, render, &directory, &[], true).unwrap();
+    save_record(record, &directory, &record_path).unwrap();
+
+    // A file modified after the generation is not touched unless forced
+    fs::write(directory.join("src/main.rs"), "fn main() { run(); }\n").unwrap();
+    let record = read_record(&directory, &record_path).unwrap();
+    let not_forced = undo_generation(&record, &directory, &record_path, false);
+    let forced = undo_generation(&record, &directory, &record_path, true);
+
+    let readme = fs::read_to_string(directory.join("README.md")).unwrap();
+    let src_exists = directory.join("src").exists();
+    let record_exists = record_path.exists();
+
+    assert!(not_forced.is_err());
+    assert!(forced.is_ok());
+    assert_eq!(readme, "# My project\n");
+    assert!(!src_exists);
+    assert!(!record_exists);
+}
+
+#[test]
+fn it_should_keep_the_previous_record_until_the_new_one_is_saved() {
+    let root = TestDir::new("undo-record");
+    let directory = root.join("project");
+    let record_path = root.join("record");
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("README.md"), "# My project\n").unwrap();
+
+    let template = get_template();
+    let render = render_template(&template, &[]).unwrap();
+    let record = record_generation(&template, &render, &[], &directory, &record_path).unwrap();
+    gen_template(&template, render, &directory, &[], true).unwrap();
+    let saved_record = save_record(record, &directory, &record_path).unwrap();
+
+    // The second generation is recorded, but it fails before its record is saved
+    let render = render_template(&template, &[]).unwrap();
+    let unsaved = record_generation(&template, &render, &[], &directory, &record_path);
+    let record = read_record(&directory, &record_path).unwrap();
+    let backup = fs::read_to_string(record_path.join("Backups/README.md")).unwrap();
+
+    assert!(unsaved.is_ok());
+    assert_eq!(record, saved_record);
+    assert_eq!(backup, "# My project\n");
+}
+
+#[test]
+fn it_should_hash_the_same_directory_written_in_different_ways() {
+    let hash = get_directory_hash(Path::new("proj")).unwrap();
+
+    for directory in ["./proj", "proj/", "./proj/", "proj/src/..", "other/../proj"] {
+        assert_eq!(get_directory_hash(Path::new(directory)).unwrap(), hash);
+    }
+    assert_ne!(get_directory_hash(Path::new("other")).unwrap(), hash);
+}
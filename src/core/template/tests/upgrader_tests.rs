@@ -0,0 +1,130 @@
+use super::engine::TempEngineArg;
+use super::make_test_template_with_files;
+use super::manifest::make_manifest;
+use super::upgrader::{upgrade_template, UpgradeAction, UpgradeChange};
+use super::Template;
+use super::TestDir;
+use std::fs;
+
+fn get_template(files: Vec<(&str, &str)>) -> Template {
+    make_test_template_with_files(&files)
+}
+
+#[test]
+fn it_should_merge_template_changes_into_modified_files() {
+    let directory = TestDir::new("upgrade");
+
+    let old_template = get_template(vec![
+        ("main.rs", "fn main() {\n    run();\n}\n\nfn run() {}\n"),
+        ("config.toml", "port = 80\n"),
+        ("old.txt", "removed upstream\n"),
+        ("notes.txt", "first\n"),
+    ]);
+    let new_template = get_template(vec![
+        (
+            "main.rs",
+            "fn main() {\n    run();\n}\n\nfn run() {\n    serve();\n}\n",
+        ),
+        ("config.toml", "port = 8080\n"),
+        ("notes.txt", "second\n"),
+        ("new.txt", "added upstream\n"),
+    ]);
+
+    // main.rs was changed by the user in a different region than the template change
+    fs::write(
+        directory.join("main.rs"),
+        "use app::run;\n\nfn main() {\n    run();\n}\n\nfn run() {}\n",
+    )
+    .unwrap();
+    fs::write(directory.join("config.toml"), "port = 80\n").unwrap();
+    fs::write(directory.join("old.txt"), "removed upstream\n").unwrap();
+    fs::write(directory.join("notes.txt"), "mine\n").unwrap();
+
+    let args: Vec<TempEngineArg> = vec![];
+    let (_, changes) = upgrade_template(&old_template, &new_template, &args, &directory).unwrap();
+    let get_action = |path: &str| {
+        changes
+            .iter()
+            .find(|change| change.path == path)
+            .map(|change: &UpgradeChange| change.action.clone())
+    };
+
+    assert_eq!(get_action("main.rs"), Some(UpgradeAction::Merged));
+    assert_eq!(get_action("config.toml"), Some(UpgradeAction::Updated));
+    assert_eq!(get_action("notes.txt"), Some(UpgradeAction::Conflicted));
+    assert_eq!(get_action("new.txt"), Some(UpgradeAction::Created));
+    assert_eq!(get_action("old.txt"), Some(UpgradeAction::Deleted));
+
+    assert_eq!(
+        fs::read_to_string(directory.join("main.rs")).unwrap(),
+        "use app::run;\n\nfn main() {\n    run();\n}\n\nfn run() {\n    serve();\n}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(directory.join("config.toml")).unwrap(),
+        "port = 8080\n"
+    );
+    assert!(fs::read_to_string(directory.join("notes.txt"))
+        .unwrap()
+        .contains("<<<<<<<"));
+    assert!(!directory.join("old.txt").exists());
+}
+
+#[test]
+fn it_should_upgrade_binary_files_without_merging() {
+    let directory = TestDir::new("upgrade-binary");
+
+    let old_logo = vec![0x89, 0x50, 0x4e, 0x47, 0xff, 0x00];
+    let new_logo = vec![0x89, 0x50, 0x4e, 0x47, 0xfe, 0x01];
+    let user_icon = vec![0x00, 0xff, 0xfe, 0x02];
+    let old_template = make_test_template_with_files(&[
+        ("logo.png", old_logo.clone()),
+        ("icon.png", old_logo.clone()),
+    ]);
+    let new_template = make_test_template_with_files(&[
+        ("logo.png", new_logo.clone()),
+        ("icon.png", new_logo.clone()),
+    ]);
+
+    fs::write(directory.join("logo.png"), &old_logo).unwrap();
+    fs::write(directory.join("icon.png"), &user_icon).unwrap();
+
+    let args: Vec<TempEngineArg> = vec![];
+    let (_, changes) = upgrade_template(&old_template, &new_template, &args, &directory).unwrap();
+    let logo = fs::read(directory.join("logo.png")).unwrap();
+    let icon = fs::read(directory.join("icon.png")).unwrap();
+
+    assert_eq!(
+        changes,
+        vec![
+            UpgradeChange {
+                path: "logo.png".to_string(),
+                action: UpgradeAction::Updated,
+            },
+            UpgradeChange {
+                path: "icon.png".to_string(),
+                action: UpgradeAction::Kept,
+            },
+        ]
+    );
+    assert_eq!(logo, new_logo);
+    assert_eq!(icon, user_icon);
+}
+
+#[test]
+fn it_should_not_return_files_deleted_by_the_user() {
+    let directory = TestDir::new("upgrade-deleted");
+
+    let old_template = get_template(vec![("main.rs", "v1\n"), ("README.md", "readme\n")]);
+    let new_template = get_template(vec![("main.rs", "v2\n"), ("README.md", "new readme\n")]);
+
+    // README.md was generated and then deleted by the user
+    fs::write(directory.join("main.rs"), "v1\n").unwrap();
+
+    let args: Vec<TempEngineArg> = vec![];
+    let (paths, _) = upgrade_template(&old_template, &new_template, &args, &directory).unwrap();
+    let manifest = make_manifest("test", &new_template, &args, &directory, &paths);
+
+    let manifest = manifest.unwrap();
+    assert_eq!(manifest.files.len(), 1);
+    assert_eq!(manifest.files[0].path, "main.rs");
+}
@@ -0,0 +1,50 @@
+use super::{TempSource, Template};
+use crate::core::path::get_trusted_templates_file_path;
+use crate::utils::errors::std_error;
+use serde_json::{from_str, to_string_pretty};
+use std::{fs, io::Error};
+
+// Only templates that come from a remote repo (or a .tpo file) and run
+// commands on the user machine need to be trusted before being generated.
+pub fn requires_trust(template: &Template) -> bool {
+    let has_hooks = match &template.hooks {
+        Some(hooks) => !hooks.is_empty(),
+        None => false,
+    };
+
+    has_hooks
+        && matches!(
+            template.source,
+            Some(TempSource::Remote { .. }) | Some(TempSource::File { .. })
+        )
+}
+
+pub fn is_trusted(template: &Template) -> Result<bool, Error> {
+    let template_hash = template.hash()?;
+    Ok(get_trusted_hashes()?.contains(&template_hash))
+}
+
+pub fn trust_template(template: &Template) -> Result<(), Error> {
+    let template_hash = template.hash()?;
+    let mut hashes = get_trusted_hashes()?;
+
+    if !hashes.contains(&template_hash) {
+        hashes.push(template_hash);
+        fs::write(
+            get_trusted_templates_file_path()?,
+            std_error(to_string_pretty(&hashes))?,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn get_trusted_hashes() -> Result<Vec<String>, Error> {
+    let trusted_filename = get_trusted_templates_file_path()?;
+
+    if !trusted_filename.exists() {
+        return Ok(vec![]);
+    }
+
+    std_error(from_str(&fs::read_to_string(trusted_filename)?))
+}
@@ -0,0 +1,270 @@
+use super::engine::{parse_path, TempEngineArg};
+use super::maker::TempData;
+use super::manifest::MANIFEST_FILENAME;
+use super::{TempPathType, Template};
+use crate::core::path::get_generations_path;
+use crate::utils::date::get_date_now_string;
+use crate::utils::errors::{invalid_input_error, not_found_error, std_error};
+use crate::utils::hash::sha256_hex;
+use crate::utils::path::{
+    format_path_namespace, normalize_path, pathbuf_to_string, str_to_pathbuf,
+};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{from_str, to_string_pretty};
+use std::{
+    env, fs,
+    io::Error,
+    path::{Path, PathBuf},
+};
+
+const RECORD_FILENAME: &str = "record.json";
+const BACKUPS_FOLDER: &str = "Backups";
+
+// Record of what the last generation in a directory created and overwrote
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GenRecord {
+    pub directory: String,
+    pub generated_at: String,
+    pub created_directory: bool,
+    pub entries: Vec<GenRecordEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GenRecordEntry {
+    pub path: String,
+    pub path_type: TempPathType,
+    pub action: GenRecordAction,
+    pub hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum GenRecordAction {
+    Created,
+    Overwritten,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum UndoAction {
+    Deleted,
+    Restored,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UndoChange {
+    pub path: String,
+    pub action: UndoAction,
+}
+
+// Each output directory keeps its record in a folder named by the directory path hash
+pub fn get_record_path(directory: &Path) -> Result<PathBuf, Error> {
+    Ok(get_generations_path()?.join(get_directory_hash(directory)?))
+}
+
+// The same directory has the same hash, however its path is written
+pub fn get_directory_hash(directory: &Path) -> Result<String, Error> {
+    let absolute_path = normalize_path(&env::current_dir()?.join(directory));
+    Ok(sha256_hex(pathbuf_to_string(absolute_path).as_bytes()))
+}
+
+// The new record is built aside and only replaces the previous one when saved,
+// so a failed generation keeps the previous generation undoable.
+fn get_staging_path(record_path: &Path) -> PathBuf {
+    let mut name = record_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".new");
+    record_path.with_file_name(name)
+}
+
+// Must be called before the generation, so the files that will be
+// overwritten are copied to the backups folder.
+pub fn record_generation(
+    template: &Template,
+    render: &TempData,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+    record_path: &Path,
+) -> Result<GenRecord, Error> {
+    let mut paths: Vec<(PathBuf, TempPathType)> = render
+        .paths
+        .iter()
+        .map(|path| (path.path.clone(), path.path_type.clone()))
+        .collect();
+
+    for injection in template.injections.iter().flatten() {
+        let file = parse_path(injection.file.clone(), temp_args.to_vec())?;
+        paths.push((str_to_pathbuf(&file), TempPathType::File));
+    }
+    paths.push((str_to_pathbuf(MANIFEST_FILENAME), TempPathType::File));
+
+    let staging_path = get_staging_path(record_path);
+    if staging_path.exists() {
+        fs::remove_dir_all(&staging_path)?;
+    }
+
+    let backups_path = staging_path.join(BACKUPS_FOLDER);
+    let mut entries: Vec<GenRecordEntry> = vec![];
+
+    for (path, path_type) in paths {
+        let path_name = pathbuf_to_string(format_path_namespace(path.clone()));
+        let file_path = directory.join(&path);
+
+        if entries.iter().any(|entry| entry.path == path_name) {
+            continue;
+        }
+
+        let action = if !file_path.exists() {
+            GenRecordAction::Created
+        } else if path_type == TempPathType::File {
+            let backup_path = backups_path.join(&path);
+            fs::create_dir_all(backup_path.parent().unwrap_or(&backups_path))?;
+            fs::copy(&file_path, backup_path)?;
+            GenRecordAction::Overwritten
+        } else {
+            // Folders that already exist are not touched by the undo
+            continue;
+        };
+
+        entries.push(GenRecordEntry {
+            path: path_name,
+            path_type,
+            action,
+            hash: None,
+        });
+    }
+
+    Ok(GenRecord {
+        directory: pathbuf_to_string(directory.to_path_buf()),
+        generated_at: get_date_now_string(),
+        created_directory: !directory.exists(),
+        entries,
+    })
+}
+
+// Saves the record with the hashes of the files as they were left by the generation
+pub fn save_record(
+    record: GenRecord,
+    directory: &Path,
+    record_path: &Path,
+) -> Result<GenRecord, Error> {
+    let entries: Result<Vec<GenRecordEntry>, Error> = record
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let file_path = directory.join(&entry.path);
+            let hash = if entry.path_type == TempPathType::File && file_path.is_file() {
+                Some(sha256_hex(&fs::read(file_path)?))
+            } else {
+                None
+            };
+
+            Ok(GenRecordEntry { hash, ..entry })
+        })
+        .collect();
+
+    let record = GenRecord {
+        entries: entries?,
+        ..record
+    };
+
+    let staging_path = get_staging_path(record_path);
+    fs::create_dir_all(&staging_path)?;
+    fs::write(
+        staging_path.join(RECORD_FILENAME),
+        std_error(to_string_pretty(&record))?,
+    )?;
+
+    if record_path.exists() {
+        fs::remove_dir_all(record_path)?;
+    }
+    fs::rename(staging_path, record_path)?;
+
+    Ok(record)
+}
+
+pub fn get_backup_path(record_path: &Path, path: &str) -> PathBuf {
+    record_path.join(BACKUPS_FOLDER).join(path)
+}
+
+pub fn read_record(directory: &Path, record_path: &Path) -> Result<GenRecord, Error> {
+    let record_filename = record_path.join(RECORD_FILENAME);
+
+    if !record_filename.exists() {
+        return Err(not_found_error(&format!(
+            "Not is possible to find a generation to undo in \"{}\".",
+            pathbuf_to_string(directory.to_path_buf())
+        )));
+    }
+
+    std_error(from_str(&fs::read_to_string(record_filename)?))
+}
+
+// Deletes the created files and restores the overwritten ones. Files that
+// were modified after the generation are only touched when forced.
+pub fn undo_generation(
+    record: &GenRecord,
+    directory: &Path,
+    record_path: &Path,
+    force: bool,
+) -> Result<Vec<UndoChange>, Error> {
+    if !force {
+        let modified_files = get_modified_files(record, directory)?;
+
+        if !modified_files.is_empty() {
+            return Err(invalid_input_error(&format!(
+                "The following files were modified after the generation:\n    - {}\nUse --force to undo anyway.",
+                modified_files.join("\n    - ")
+            )));
+        }
+    }
+
+    let mut changes = vec![];
+
+    for entry in record.entries.iter().rev() {
+        let file_path = directory.join(&entry.path);
+
+        let action = match (&entry.action, &entry.path_type) {
+            (GenRecordAction::Overwritten, _) => {
+                fs::copy(get_backup_path(record_path, &entry.path), &file_path)?;
+                Some(UndoAction::Restored)
+            }
+            (GenRecordAction::Created, TempPathType::File) if file_path.is_file() => {
+                fs::remove_file(&file_path)?;
+                Some(UndoAction::Deleted)
+            }
+            // Only empty folders are removed
+            (GenRecordAction::Created, TempPathType::Dir) if file_path.is_dir() => {
+                fs::remove_dir(&file_path).ok().map(|_| UndoAction::Deleted)
+            }
+            _ => None,
+        };
+
+        if let Some(action) = action {
+            changes.push(UndoChange {
+                path: entry.path.clone(),
+                action,
+            });
+        }
+    }
+
+    if record.created_directory {
+        let _ = fs::remove_dir(directory);
+    }
+
+    fs::remove_dir_all(record_path)?;
+    Ok(changes)
+}
+
+fn get_modified_files(record: &GenRecord, directory: &Path) -> Result<Vec<String>, Error> {
+    let mut modified_files = vec![];
+
+    for entry in record.entries.iter() {
+        let file_path = directory.join(&entry.path);
+
+        if let Some(hash) = &entry.hash {
+            if file_path.is_file() && sha256_hex(&fs::read(&file_path)?) != *hash {
+                modified_files.push(entry.path.clone());
+            }
+        }
+    }
+
+    Ok(modified_files)
+}
@@ -0,0 +1,161 @@
+use super::engine::TempEngineArg;
+use super::generator::{get_rendered_files, render_template};
+use super::{TempPath, TempPathType, Template};
+use crate::utils::path::get_path_name;
+use std::{fs, io::Error, path::Path, str};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum UpgradeAction {
+    Created,
+    Updated,
+    Merged,
+    Conflicted,
+    Deleted,
+    Kept,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpgradeChange {
+    pub path: String,
+    pub action: UpgradeAction,
+}
+
+// Three-way merge between the old template render (base), the files in the
+// directory (ours) and the new template render (theirs).
+// Returns the new rendered paths that exist in the directory after the upgrade
+// (files removed by the user are not brought back) and the applied changes.
+pub fn upgrade_template(
+    old_template: &Template,
+    new_template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+) -> Result<(Vec<TempPath>, Vec<UpgradeChange>), Error> {
+    let old_render = render_template(old_template, temp_args)?;
+    let new_render = render_template(new_template, temp_args)?;
+    let old_files = get_rendered_files(&old_render)?;
+    let new_files = get_rendered_files(&new_render)?;
+
+    let mut changes = vec![];
+
+    for path in new_render.paths.iter() {
+        if path.path_type == TempPathType::Dir {
+            fs::create_dir_all(directory.join(&path.path))?;
+            continue;
+        }
+
+        let file_name = get_path_name(&path.path);
+        let new_bytes = &new_files[&file_name];
+        let old_bytes = old_files.get(&file_name);
+        let file_path = directory.join(&path.path);
+
+        let current_bytes = if file_path.exists() {
+            Some(fs::read(&file_path)?)
+        } else {
+            None
+        };
+
+        let action = match (old_bytes, current_bytes) {
+            // File was removed by the user, so it is not brought back
+            (Some(_), None) => None,
+            (None, None) => {
+                fs::write(&file_path, new_bytes)?;
+                Some(UpgradeAction::Created)
+            }
+            (old_bytes, Some(current_bytes)) => {
+                let old_bytes = old_bytes.map(Vec::as_slice).unwrap_or_default();
+
+                if current_bytes == *new_bytes || old_bytes == new_bytes.as_slice() {
+                    None
+                } else if current_bytes == old_bytes {
+                    fs::write(&file_path, new_bytes)?;
+                    Some(UpgradeAction::Updated)
+                } else {
+                    Some(merge_file(
+                        &file_path,
+                        old_bytes,
+                        &current_bytes,
+                        new_bytes,
+                    )?)
+                }
+            }
+        };
+
+        if let Some(action) = action {
+            changes.push(UpgradeChange {
+                path: file_name,
+                action,
+            });
+        }
+    }
+
+    // Files that do not exist in the new template version
+    for path in old_render.paths.iter().rev() {
+        let path_name = get_path_name(&path.path);
+        let file_path = directory.join(&path.path);
+
+        if new_render
+            .paths
+            .iter()
+            .any(|new| get_path_name(&new.path) == path_name)
+            || !file_path.exists()
+        {
+            continue;
+        }
+
+        if path.path_type == TempPathType::Dir {
+            // Only empty folders are removed
+            let _ = fs::remove_dir(&file_path);
+            continue;
+        }
+
+        let action = if fs::read(&file_path)? == old_files[&path_name] {
+            fs::remove_file(&file_path)?;
+            UpgradeAction::Deleted
+        } else {
+            UpgradeAction::Kept
+        };
+
+        changes.push(UpgradeChange {
+            path: path_name,
+            action,
+        });
+    }
+
+    let paths = new_render
+        .paths
+        .into_iter()
+        .filter(|path| directory.join(&path.path).exists())
+        .collect();
+
+    Ok((paths, changes))
+}
+
+// Only text files are merged, binary files changed on both sides keep the user version
+fn merge_file(
+    file_path: &Path,
+    old_bytes: &[u8],
+    current_bytes: &[u8],
+    new_bytes: &[u8],
+) -> Result<UpgradeAction, Error> {
+    let texts = (
+        str::from_utf8(old_bytes),
+        str::from_utf8(current_bytes),
+        str::from_utf8(new_bytes),
+    );
+
+    Ok(match texts {
+        (Ok(old_text), Ok(current_text), Ok(new_text)) => {
+            match diffy::merge(old_text, current_text, new_text) {
+                Ok(merged) => {
+                    fs::write(file_path, merged)?;
+                    UpgradeAction::Merged
+                }
+                Err(conflicted) => {
+                    fs::write(file_path, conflicted)?;
+                    UpgradeAction::Conflicted
+                }
+            }
+        }
+        _ => UpgradeAction::Kept,
+    })
+}
@@ -8,7 +8,7 @@ use crate::core::repos::Repository;
 use crate::utils::errors::invalid_input_error;
 use cli::input::command::parse_command;
 use methods::*;
-use std::env;
+use std::{env, process};
 
 #[tokio::main]
 async fn main() {
@@ -46,11 +46,24 @@ async fn main() {
     }
 
     if let Some(method) = command.method.clone() {
+        let mut exit_code = 0;
         let output = {
             match method.as_str() {
                 "gen" => Generate::run(command).await,
                 "get" => Get::run(command).await,
                 "del" => Delete::run(command),
+                // Differences and lint errors are reported with a non-zero exit code,
+                // so they can be used in CI
+                "diff" => Diff::run(command).map(|has_diffs| {
+                    if has_diffs {
+                        exit_code = 1;
+                    }
+                }),
+                "lint" => Lint::run(command).map(|has_errors| {
+                    if has_errors {
+                        exit_code = 1;
+                    }
+                }),
                 "regs" => Registry::run(command),
                 "repo" => Repo::run(command),
                 "repos" => Repos::run(command),
@@ -58,7 +71,9 @@ async fn main() {
                 "view" => View::run(command),
                 "docs" => Docs::run(command),
                 "save" => Save::run(command),
+                "undo" => Undo::run(command),
                 "update" => Update::run(command),
+                "upgrade" => Upgrade::run(command),
                 _ => Err(invalid_input_error(&format!(
                     "Invalid method \"{}\".",
                     method
@@ -68,6 +83,11 @@ async fn main() {
 
         if let Err(e) = output {
             eprintln!("{}: {}", paint_string!("{red}", "Error"), e);
+            exit_code = 1;
+        }
+
+        if exit_code != 0 {
+            process::exit(exit_code);
         }
     }
 }
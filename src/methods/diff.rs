@@ -0,0 +1,88 @@
+use super::generate::{get_template_args_by_options, get_template_args_by_temp};
+use crate::cli::input::check_flags;
+use crate::cli::input::command::Command;
+use crate::cli::input::namespaces::{get_repo_namespace_obj, NamespaceObject};
+use crate::cli::output::messages::error::INVALID_TEMPLATE_NAME;
+use crate::core::repos::Repository;
+use crate::core::template::differ::{diff_template, DiffStatus, FileDiff};
+use crate::core::template::inheritance::resolve_template;
+use crate::utils::errors::invalid_input_error;
+use crate::{paint_string, write_help};
+use std::io::Error;
+
+pub struct Diff;
+
+impl Diff {
+    pub fn help() {
+        write_help!("../../help_files/diff.json");
+    }
+
+    // Returns true when differences were found
+    pub fn run(command: Command) -> Result<bool, Error> {
+        if command.has_help_flag() {
+            Self::help();
+            return Ok(false);
+        }
+
+        check_flags(&command.flags, vec![])?;
+
+        if command.args.is_empty() {
+            return Err(invalid_input_error(INVALID_TEMPLATE_NAME));
+        }
+
+        let directory = if command.args.len() < 2 {
+            "."
+        } else {
+            command.args[1].as_str()
+        };
+
+        let NamespaceObject {
+            repo_name,
+            template_name,
+        } = get_repo_namespace_obj(&command.args[0]);
+        let repo = Repository::connect(repo_name)?;
+        let template = resolve_template(repo.get_template(&template_name)?)?;
+
+        let temp_args = if !command.options.is_empty() {
+            get_template_args_by_options(command.options.clone(), &template)?
+        } else {
+            get_template_args_by_temp(&template, false)?
+        };
+
+        let diffs = diff_template(&template, &temp_args, directory)?;
+
+        if diffs.is_empty() {
+            println!("No differences found.");
+            return Ok(false);
+        }
+
+        diffs.iter().for_each(|diff| print!("{}", diff.patch));
+        println!();
+        display_summary(&diffs);
+
+        Ok(true)
+    }
+}
+
+fn display_summary(diffs: &[FileDiff]) {
+    let count = |status: DiffStatus| diffs.iter().filter(|diff| diff.status == status).count();
+
+    diffs.iter().for_each(display_diff_path);
+
+    println!(
+        "\n{} added, {} removed, {} changed.",
+        count(DiffStatus::Added),
+        count(DiffStatus::Removed),
+        count(DiffStatus::Changed)
+    );
+}
+
+fn display_diff_path(diff: &FileDiff) {
+    let label = match diff.status {
+        DiffStatus::Added => paint_string!("{green}", "added:   "),
+        DiffStatus::Removed => paint_string!("{red}", "removed: "),
+        DiffStatus::Changed => paint_string!("{yellow}", "changed: "),
+    };
+
+    println!("{}{}", label, diff.path);
+}
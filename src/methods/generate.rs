@@ -1,19 +1,41 @@
 use serde_json::from_str;
 
-use crate::cli::input::check_flags;
+use crate::cli::input::{check_flags, prompt_boolean};
 use crate::cli::input::command::{Command, CommandOption};
-use crate::cli::input::namespaces::{get_repo_namespace_obj, parse_namespace_to_raw_url};
+use crate::cli::input::namespaces::{
+    get_repo_namespace_obj, parse_namespace_to_raw_url, NamespaceObject,
+};
 use crate::cli::output::messages::error::{INVALID_DIRECTORY_PATH_TYPE, INVALID_TEMPLATE_NAME};
 use crate::core::repos::remote_repos_reg::get_reg;
 use crate::core::repos::Repository;
 use crate::core::http::{str_is_url, validate_url};
+use crate::core::template::composer::{compose_templates, ConflictPolicy};
 use crate::core::template::engine::{get_engine_args_input, set_arg_default_value, TempEngineArg};
+use crate::core::template::filter::filter_template;
 use crate::core::template::getter::get_remote_template;
-use crate::core::template::{generator, Template};
-use crate::utils::errors::{invalid_input_error, std_error};
-use crate::{paintln, write_help};
+use crate::core::template::inheritance::resolve_template;
+use crate::core::template::manifest::{make_manifest, write_manifest};
+use crate::core::template::report::{make_report, report_to_json};
+use crate::core::template::sink::{ArchiveSink, StdoutSink};
+use crate::core::template::snapshot::save_snapshot;
+use crate::core::template::undo::GenRecord;
+use crate::core::template::{
+    generator, hooks, requirements, trust, undo, TempPath, TempSource, Template,
+};
+use crate::utils::errors::{invalid_input_error, other_error, std_error};
+use crate::{paint_string, paintln, write_help};
 use std::{fs, io::Error, path::Path, time::Instant};
 
+// Options used by the gen command itself, so they are not template args
+const RESERVED_OPTIONS: [&str; 6] = [
+    "key",
+    "conflict",
+    "only",
+    "exclude",
+    "output-archive",
+    "report",
+];
+
 pub struct Generate;
 
 impl Generate {
@@ -27,7 +49,7 @@ impl Generate {
             return Ok(());
         }
 
-        let flags = vec!["--file", "-f", "--remote"];
+        let flags = vec!["--file", "-f", "--remote", "--no-hooks", "--trust", "--stdout"];
         check_flags(&command.flags, flags)?;
 
         if command.has_flag("-f") || command.has_flag("--file") {
@@ -54,25 +76,29 @@ impl Generate {
             return Err(invalid_input_error(INVALID_DIRECTORY_PATH_TYPE));
         }
 
-        let template_namespace = get_repo_namespace_obj(&command.args[0]);
-        let repo = Repository::connect(template_namespace.repo_name)?;
-        let template = repo.get_template(&template_namespace.template_name)?;
-
-        let temp_args = if !command.options.is_empty() {
-            get_template_args_by_options(command.options, &template)?
-        } else {
-            get_template_args_by_temp(&template)?
-        };
+        let namespaces: Vec<&str> = command.args[0].split(',').collect();
+        let template = get_composed_template(&command, &namespaces)?;
+        let template = filter_template_by_options(&command, template)?;
+        check_template_requirements(&template)?;
+        confirm_template_trust(&command, &template)?;
 
-        generator::gen_template(template, directory, temp_args)?;
-        println!(
-            "Template \"{}\" was generated.",
-            template_namespace.template_name
-        );
+        let temp_args = get_template_args(&command, &template)?;
+        if gen_to_output_sink(&command, &template, &temp_args, start)? {
+            return Ok(());
+        }
 
-        let end = Instant::now(); // stop timing process
-        println!("Done in {:.2?}", end.duration_since(start));
-        Ok(())
+        let namespace = namespaces
+            .iter()
+            .map(|namespace| {
+                let NamespaceObject {
+                    repo_name,
+                    template_name,
+                } = get_repo_namespace_obj(namespace);
+                format!("{}/{}", repo_name, template_name)
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        gen_and_finish(&command, &namespace, &template, &temp_args, directory, start)
     }
 }
 
@@ -95,20 +121,22 @@ fn gen_from_template_file(command: Command) -> Result<(), Error> {
     }
 
     let template_string = fs::read_to_string(tpo_filename)?;
-    let template: Template = std_error(from_str(&template_string))?;
-
-    let temp_args = if !command.options.is_empty() {
-        get_template_args_by_options(command.options, &template)?
-    } else {
-        get_template_args_by_temp(&template)?
-    };
-
-    generator::gen_template(template.clone(), directory, temp_args)?;
-    println!("Template \"{}\" was generated.", template.name);
+    let mut template: Template = std_error(from_str(&template_string))?;
+    // The source written in the file is not trusted, anyone can edit it
+    template.source = Some(TempSource::File {
+        path: tpo_filename.to_string(),
+    });
+    let template = resolve_template(template)?;
+    let template = filter_template_by_options(&command, template)?;
+    check_template_requirements(&template)?;
+    confirm_template_trust(&command, &template)?;
+
+    let temp_args = get_template_args(&command, &template)?;
+    if gen_to_output_sink(&command, &template, &temp_args, start)? {
+        return Ok(());
+    }
 
-    let end = Instant::now(); // stop timing process
-    println!("Done in {:.2?}", end.duration_since(start));
-    Ok(())
+    gen_and_finish(&command, tpo_filename, &template, &temp_args, directory, start)
 }
 
 async fn gen_from_remote_template(command: Command) -> Result<(), Error> {
@@ -154,29 +182,299 @@ async fn gen_from_remote_template(command: Command) -> Result<(), Error> {
         None
     };
 
-    paintln!("{gray}", "[getting template]");
-    let template = get_remote_template(&url, key).await?.template;
+    if !command.has_flag("--stdout") && !command.has_option("report") {
+        paintln!("{gray}", "[getting template]");
+    }
+    let mut template = resolve_template(get_remote_template(&url, key).await?.template)?;
+    template.source = Some(TempSource::Remote { url: url.clone() });
+    let template = filter_template_by_options(&command, template)?;
+    check_template_requirements(&template)?;
+    confirm_template_trust(&command, &template)?;
+
+    let temp_args = get_template_args(&command, &template)?;
+    if gen_to_output_sink(&command, &template, &temp_args, start)? {
+        return Ok(());
+    }
 
-    let temp_args = if !command.options.is_empty() {
-        get_template_args_by_options(command.options.clone(), &template)?
-    } else {
-        get_template_args_by_temp(&template)?
+    gen_and_finish(&command, &url, &template, &temp_args, directory, start)
+}
+
+// Several namespaces separated by commas are composed, in order, into a single template
+fn get_composed_template(command: &Command, namespaces: &[&str]) -> Result<Template, Error> {
+    let mut templates = vec![];
+
+    for namespace in namespaces.iter() {
+        let NamespaceObject {
+            repo_name,
+            template_name,
+        } = get_repo_namespace_obj(namespace);
+        let repo = Repository::connect(repo_name)?;
+        templates.push(resolve_template(repo.get_template(&template_name)?)?);
+    }
+
+    let policy = match command.get_opt_by_name("conflict") {
+        Some(option) => ConflictPolicy::from_name(&option.value)?,
+        None => ConflictPolicy::Override,
+    };
+
+    compose_templates(templates, &policy)
+}
+
+// Generates only a subset of the template when --only or --exclude globs are informed
+fn filter_template_by_options(command: &Command, template: Template) -> Result<Template, Error> {
+    let only = command.get_opt_by_name("only").map(|option| option.value.as_str());
+    let exclude = command
+        .get_opt_by_name("exclude")
+        .map(|option| option.value.as_str());
+
+    if only.is_none() && exclude.is_none() {
+        return Ok(template);
+    }
+
+    filter_template(template, only, exclude)
+}
+
+fn generates_to_sink(command: &Command) -> bool {
+    command.has_option("output-archive") || command.has_flag("--stdout")
+}
+
+// Generates into an archive or to stdout, without touching the output folder, so
+// there are no manifest, injections or hooks. Returns false if it is not the case.
+fn gen_to_output_sink(
+    command: &Command,
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    start: Instant,
+) -> Result<bool, Error> {
+    if let Some(option) = command.get_opt_by_name("output-archive") {
+        let mut sink = ArchiveSink::new(Path::new(&option.value))?;
+        generator::write_template(template, temp_args, &mut sink)?;
+        println!(
+            "\nTemplate \"{}\" was generated into \"{}\".",
+            template.name, option.value
+        );
+
+        let end = Instant::now(); // stop timing process
+        println!("Done in {:.2?}", end.duration_since(start));
+        return Ok(true);
+    }
+
+    if command.has_flag("--stdout") {
+        generator::write_template(template, temp_args, &mut StdoutSink::default())?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+// Generates the template into the output folder and runs its hooks. With
+// --report=json the generation log is replaced by a JSON report.
+fn gen_and_finish(
+    command: &Command,
+    template_namespace: &str,
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+    start: Instant,
+) -> Result<(), Error> {
+    let report = match command.get_opt_by_name("report") {
+        Some(option) if option.value == "json" => true,
+        Some(option) => {
+            return Err(invalid_input_error(&format!(
+                "Invalid report format \"{}\", only json is supported.",
+                option.value
+            )))
+        }
+        None => false,
     };
 
-    generator::gen_template(template.clone(), directory, temp_args)?;
-    println!("Template \"{}\" was generated.", template.name);
+    let record_path = undo::get_record_path(directory)?;
+    let record = gen_and_record(
+        template_namespace,
+        template,
+        temp_args,
+        directory,
+        &record_path,
+        report,
+    )?;
+
+    if !report {
+        println!("Template \"{}\" was generated.", template.name);
+    }
+
+    run_template_hooks(command, template, directory, temp_args, report)?;
 
     let end = Instant::now(); // stop timing process
-    println!("Done in {:.2?}", end.duration_since(start));
-    return Ok(());
+    if report {
+        let gen_report = make_report(
+            template_namespace,
+            template,
+            temp_args,
+            directory,
+            &record,
+            &record_path,
+            end.duration_since(start),
+        )?;
+        println!("{}", report_to_json(&gen_report)?);
+    } else {
+        println!("Done in {:.2?}", end.duration_since(start));
+    }
+
+    Ok(())
+}
+
+// Generates the template, keeping a record of the generation so it can be undone
+fn gen_and_record(
+    template_namespace: &str,
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+    record_path: &Path,
+    quiet: bool,
+) -> Result<GenRecord, Error> {
+    let render = generator::render_template(template, temp_args)?;
+    let record = undo::record_generation(template, &render, temp_args, directory, record_path)?;
+
+    let generated_paths =
+        generator::gen_template(template, render, directory, temp_args, quiet)?;
+    write_gen_manifest(
+        template_namespace,
+        template,
+        temp_args,
+        directory,
+        &generated_paths,
+    )?;
+
+    undo::save_record(record, directory, record_path)
+}
+
+fn write_gen_manifest(
+    template_namespace: &str,
+    template: &Template,
+    temp_args: &[TempEngineArg],
+    directory: &Path,
+    generated_paths: &[TempPath],
+) -> Result<(), Error> {
+    let manifest = make_manifest(
+        template_namespace,
+        template,
+        temp_args,
+        directory,
+        generated_paths,
+    )?;
+    write_manifest(directory, &manifest)?;
+    save_snapshot(template)
 }
 
-fn get_template_args_by_options(
+fn check_template_requirements(template: &Template) -> Result<(), Error> {
+    if let Some(template_requirements) = &template.requirements {
+        requirements::check_requirements(template_requirements)?;
+    }
+
+    Ok(())
+}
+
+// With --report the prompts go to stderr, so stdout only has the JSON report
+fn prompts_to_stderr(command: &Command) -> bool {
+    command.has_option("report")
+}
+
+fn confirm_template_trust(command: &Command, template: &Template) -> Result<(), Error> {
+    // Hooks only run when generating into a folder
+    if command.has_flag("--no-hooks") || generates_to_sink(command) || !trust::requires_trust(template)
+    {
+        return Ok(());
+    }
+
+    if trust::is_trusted(template)? {
+        return Ok(());
+    }
+
+    if !command.has_flag("--trust") {
+        let to_stderr = prompts_to_stderr(command);
+        let mut lines = vec![];
+
+        let origin = match &template.source {
+            Some(TempSource::Remote { url }) => Some(url),
+            Some(TempSource::File { path }) => Some(path),
+            _ => None,
+        };
+        if let Some(origin) = origin {
+            lines.push(format!(
+                "{}: template \"{}\" comes from \"{}\" and runs the following commands:",
+                paint_string!("{yellow}", "Warning"),
+                template.name,
+                origin
+            ));
+        }
+
+        for hook in template.hooks.iter().flatten() {
+            lines.push(format!("    $ {}", hook));
+        }
+
+        if to_stderr {
+            eprintln!("{}", lines.join("\n"));
+        } else {
+            println!("{}", lines.join("\n"));
+        }
+
+        if !prompt_boolean("Do you trust this template? [y/n]: ", to_stderr)? {
+            return Err(other_error(
+                "Template was not trusted. Use --no-hooks to generate it without running its hooks.",
+            ));
+        }
+    }
+
+    trust::trust_template(template)
+}
+
+fn run_template_hooks(
+    command: &Command,
+    template: &Template,
+    directory: &Path,
+    temp_args: &[TempEngineArg],
+    quiet: bool,
+) -> Result<(), Error> {
+    if command.has_flag("--no-hooks") {
+        return Ok(());
+    }
+
+    if let Some(template_hooks) = &template.hooks {
+        hooks::run_hooks(template_hooks, directory, temp_args, quiet)?;
+    }
+
+    Ok(())
+}
+
+fn get_template_args(command: &Command, template: &Template) -> Result<Vec<TempEngineArg>, Error> {
+    if has_template_options(command) {
+        return get_template_args_by_options(command.options.clone(), template);
+    }
+
+    // The questions would be mixed with the generated content
+    if command.has_flag("--stdout") && template.args.iter().flatten().next().is_some() {
+        return Err(invalid_input_error(
+            "The template args must be informed as options when generating to stdout.",
+        ));
+    }
+
+    get_template_args_by_temp(template, prompts_to_stderr(command))
+}
+
+fn has_template_options(command: &Command) -> bool {
+    command
+        .options
+        .iter()
+        .any(|option| !RESERVED_OPTIONS.contains(&option.name.as_str()))
+}
+
+pub fn get_template_args_by_options(
     options: Vec<CommandOption>,
     template: &Template,
 ) -> Result<Vec<TempEngineArg>, Error> {
     options
         .into_iter()
+        .filter(|option| !RESERVED_OPTIONS.contains(&option.name.as_str()))
         .map(|option| TempEngineArg {
             key: option.name,
             value: option.value,
@@ -191,9 +489,12 @@ fn get_template_args_by_options(
         .collect()
 }
 
-fn get_template_args_by_temp(template: &Template) -> Result<Vec<TempEngineArg>, Error> {
+pub fn get_template_args_by_temp(
+    template: &Template,
+    to_stderr: bool,
+) -> Result<Vec<TempEngineArg>, Error> {
     if let Some(config_args) = &template.args {
-        let temp_args = get_engine_args_input(config_args)?
+        let temp_args = get_engine_args_input(config_args, to_stderr)?
             .into_iter()
             .map(|arg| set_arg_default_value(arg, config_args));
 
@@ -4,6 +4,7 @@ use crate::core::repos::remote_repos_reg::get_reg;
 use crate::core::repos::Repository;
 use crate::core::http::validate_url;
 use crate::core::template::getter::get_remote_template;
+use crate::core::template::TempSource;
 use crate::write_help;
 use crate::{
     paintln,
@@ -73,7 +74,8 @@ impl Get {
         let response = get_remote_template(&url, key).await?;
 
         
-        let template = response.template;
+        let mut template = response.template;
+        template.source = Some(TempSource::Remote { url });
         let repo_name = if command.args.len() > 1 {
             command.args[1].clone()
         } else {
@@ -0,0 +1,70 @@
+use crate::cli::input::check_flags;
+use crate::cli::input::command::Command;
+use crate::cli::input::namespaces::{get_repo_namespace_obj, NamespaceObject};
+use crate::core::repos::Repository;
+use crate::core::template::inheritance::resolve_template;
+use crate::core::template::lint::{lint_directory, lint_template, LintIssue, LintLevel};
+use crate::{paint_string, write_help};
+use std::{io::Error, path::Path};
+
+pub struct Lint;
+
+impl Lint {
+    pub fn help() {
+        write_help!("../../help_files/lint.json");
+    }
+
+    // Returns true when errors were found
+    pub fn run(command: Command) -> Result<bool, Error> {
+        if command.has_help_flag() {
+            Self::help();
+            return Ok(false);
+        }
+
+        check_flags(&command.flags, vec![])?;
+
+        let target = if command.args.is_empty() {
+            "."
+        } else {
+            command.args[0].as_str()
+        };
+
+        // A folder is linted before it is saved, otherwise the target is a template namespace
+        let issues = if Path::new(target).is_dir() {
+            lint_directory(target)?
+        } else {
+            let NamespaceObject {
+                repo_name,
+                template_name,
+            } = get_repo_namespace_obj(target);
+            let repo = Repository::connect(repo_name)?;
+            lint_template(&resolve_template(repo.get_template(&template_name)?)?)?
+        };
+
+        if issues.is_empty() {
+            println!("No problems found.");
+            return Ok(false);
+        }
+
+        issues.iter().for_each(display_issue);
+
+        let count = |level: LintLevel| issues.iter().filter(|issue| issue.level == level).count();
+        let errors = count(LintLevel::Error);
+        println!(
+            "\n{} errors, {} warnings.",
+            errors,
+            count(LintLevel::Warning)
+        );
+
+        Ok(errors > 0)
+    }
+}
+
+fn display_issue(issue: &LintIssue) {
+    let label = match issue.level {
+        LintLevel::Error => paint_string!("{red}", "error:   "),
+        LintLevel::Warning => paint_string!("{yellow}", "warning: "),
+    };
+
+    println!("{}{}", label, issue.message);
+}
@@ -1,26 +1,34 @@
 mod delete;
+mod diff;
 mod docs;
 mod generate;
 mod get;
+mod lint;
 mod r#move;
 mod registry;
 mod repo;
 mod repos;
 mod save;
+mod undo;
 mod update;
+mod upgrade;
 mod version;
 mod view;
 pub mod welcome;
 
 pub use delete::Delete;
+pub use diff::Diff;
 pub use docs::Docs;
 pub use generate::Generate;
 pub use get::Get;
+pub use lint::Lint;
 pub use r#move::Move;
 pub use registry::Registry;
 pub use repo::Repo;
 pub use repos::Repos;
 pub use save::Save;
+pub use undo::Undo;
 pub use update::Update;
+pub use upgrade::Upgrade;
 pub use version::Version;
 pub use view::View;
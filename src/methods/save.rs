@@ -1,9 +1,21 @@
 use crate::cli::input;
 use crate::cli::input::command::Command;
+use crate::cli::input::{check_flags, get_boolean_input};
 use crate::core::repos::Repository;
-use crate::utils::errors::invalid_input_error;
-use crate::write_help;
-use crate::{core::template::maker::make_template, utils::errors::already_exists_error};
+use crate::core::template::config::ConfigAllowedSecret;
+use crate::core::template::inheritance::resolve_template;
+use crate::core::template::maker::{
+    get_git_template_allowed_secrets, get_template_allowed_secrets, get_template_manifest,
+    make_template, make_template_from_git,
+};
+use crate::core::template::replacer::TempReplacement;
+use crate::core::template::secrets::scan_secrets;
+use crate::core::template::size::{
+    format_size, get_biggest_files, get_total_size, parse_size, TempSizeLimits,
+};
+use crate::core::template::Template;
+use crate::utils::errors::{already_exists_error, invalid_input_error, other_error};
+use crate::{paint_string, write_help};
 use std::{io::Error, time::Instant};
 
 pub struct Save;
@@ -19,8 +31,32 @@ impl Save {
             return Ok(());
         }
 
+        let flags = vec!["--no-gitignore", "--force", "--allow-secrets"];
+        check_flags(&command.flags, flags)?;
+
+        if command.get_opt_by_name("rev").is_some() && command.get_opt_by_name("git").is_none() {
+            return Err(invalid_input_error(
+                "The --rev option can only be used with --git.",
+            ));
+        }
+
+        let ref_path = if command.args.is_empty() {
+            "."
+        } else {
+            command.args[0].as_str()
+        };
+
+        // With a manifest nothing is asked, the missing settings use their defaults
+        let manifest = if command.get_opt_by_name("git").is_some() {
+            None
+        } else {
+            get_template_manifest(ref_path)?
+        };
+
         let template_name = if command.has_option("name") {
             command.get_opt_by_name("name").unwrap().value.clone()
+        } else if let Some(name) = manifest.as_ref().and_then(|manifest| manifest.name.clone()) {
+            name
         } else {
             input::get("Template name: ")?
         };
@@ -37,6 +73,8 @@ impl Save {
 
         let repo_name = if command.has_option("repo") {
             command.get_opt_by_name("repo").unwrap().value.clone()
+        } else if manifest.is_some() {
+            String::new()
         } else {
             input::get("Repository (main): ")?
         };
@@ -60,6 +98,9 @@ impl Save {
                 .unwrap()
                 .value
                 .clone()
+        } else if manifest.is_some() {
+            // The manifest description is set by the template maker
+            String::new()
         } else {
             input::get("Template description: ")?
         };
@@ -70,15 +111,44 @@ impl Save {
             Some(description_value)
         };
 
-        let ref_path = if command.args.is_empty() {
-            "."
-        } else {
-            command.args[0].as_str()
+        let start = Instant::now(); // start timing process
+        let replacements = get_replacements(&command)?;
+        let limits = get_size_limits(&command)?;
+
+        let (template, allowed_secrets) = match command.get_opt_by_name("git") {
+            Some(git) => {
+                let rev = match command.get_opt_by_name("rev") {
+                    Some(rev) => rev.value.as_str(),
+                    None => "HEAD",
+                };
+                let template = make_template_from_git(
+                    template_name,
+                    &git.value,
+                    rev,
+                    description,
+                    &replacements,
+                    &limits,
+                )?;
+                (template, get_git_template_allowed_secrets(&git.value, rev)?)
+            }
+            None => {
+                let template = make_template(
+                    template_name,
+                    ref_path,
+                    description,
+                    !command.has_flag("--no-gitignore"),
+                    &replacements,
+                    &limits,
+                )?;
+                (template, get_template_allowed_secrets(ref_path)?)
+            }
         };
 
-        let start = Instant::now(); // start timing process
-        let template = make_template(template_name, ref_path, description)?;
+        // Checks that the base templates exist, they are only applied at generation time
+        resolve_template(template.clone())?;
 
+        display_size_summary(&template);
+        confirm_template_secrets(&command, &template, &allowed_secrets)?;
         repo.save_template(template)?;
         println!("Template was saved successfully.");
 
@@ -88,3 +158,94 @@ impl Save {
         Ok(())
     }
 }
+
+// The --replace option can be repeated, one for each literal
+pub fn get_replacements(command: &Command) -> Result<Vec<TempReplacement>, Error> {
+    command
+        .options
+        .iter()
+        .filter(|option| option.name == "replace")
+        .map(|option| TempReplacement::parse(&option.value))
+        .collect()
+}
+
+// With --force the size limits are not checked
+pub fn get_size_limits(command: &Command) -> Result<TempSizeLimits, Error> {
+    if command.has_flag("--force") {
+        return Ok(TempSizeLimits::unlimited());
+    }
+
+    let mut limits = TempSizeLimits::default();
+
+    if let Some(option) = command.get_opt_by_name("max-file-size") {
+        limits.max_file_size = Some(parse_size(&option.value)?);
+    }
+
+    if let Some(option) = command.get_opt_by_name("max-size") {
+        limits.max_total_size = Some(parse_size(&option.value)?);
+    }
+
+    Ok(limits)
+}
+
+// The biggest files are listed before saving, so big files that should be
+// ignored are noticed before every listing of the repository gets slow.
+pub fn display_size_summary(template: &Template) {
+    let biggest_files = get_biggest_files(&template.contents, 5);
+
+    if biggest_files.is_empty() {
+        return;
+    }
+
+    println!(
+        "Template files: {} in {} files",
+        format_size(get_total_size(&template.contents)),
+        template.contents.len()
+    );
+    println!("Biggest files:");
+    for (file_path, size) in biggest_files.iter() {
+        println!("  {:>10}  {}", format_size(*size), file_path);
+    }
+}
+
+// The credentials found in the files are listed before saving, the template is
+// only saved when they are confirmed or allowed in TemplateConfig/secrets.json.
+pub fn confirm_template_secrets(
+    command: &Command,
+    template: &Template,
+    allowed_secrets: &[ConfigAllowedSecret],
+) -> Result<(), Error> {
+    let attributes = template.attributes.clone().unwrap_or_default();
+    let findings = scan_secrets(&template.contents, &attributes, allowed_secrets)?;
+
+    if findings.is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "{}: {} possible secrets were found in the template files:",
+        paint_string!("{yellow}", "Warning"),
+        findings.len()
+    );
+    for finding in findings.iter() {
+        println!(
+            "    {}:{}  {}  {}",
+            finding.file_path,
+            finding.line,
+            finding.rule,
+            finding.masked_text()
+        );
+    }
+
+    if command.has_flag("--allow-secrets") {
+        return Ok(());
+    }
+
+    if !get_boolean_input("Save the template with these secrets? [y/n]: ")? {
+        return Err(other_error(
+            "Template was not saved. Remove the secrets or allow them in TemplateConfig/secrets.json.",
+        ));
+    }
+
+    Ok(())
+}
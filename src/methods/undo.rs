@@ -0,0 +1,61 @@
+use crate::cli::input::check_flags;
+use crate::cli::input::command::Command;
+use crate::core::template::undo::{
+    get_record_path, read_record, undo_generation, UndoAction, UndoChange,
+};
+use crate::{paint_string, paintln, write_help};
+use std::{io::Error, path::Path, time::Instant};
+
+pub struct Undo;
+
+impl Undo {
+    pub fn help() {
+        write_help!("../../help_files/undo.json");
+    }
+
+    pub fn run(command: Command) -> Result<(), Error> {
+        if command.has_help_flag() {
+            Self::help();
+            return Ok(());
+        }
+
+        check_flags(&command.flags, vec!["--force"])?;
+
+        let start = Instant::now(); // start timing process
+
+        let directory = if command.args.is_empty() {
+            Path::new(".")
+        } else {
+            Path::new(&command.args[0])
+        };
+
+        let record_path = get_record_path(directory)?;
+        let record = read_record(directory, &record_path)?;
+
+        paintln!("{gray}", "[undoing generation...]");
+        let changes = undo_generation(
+            &record,
+            directory,
+            &record_path,
+            command.has_flag("--force"),
+        )?;
+        changes.iter().for_each(display_change);
+        println!();
+
+        println!("Generation of {} was undone.", record.generated_at);
+
+        let end = Instant::now(); // stop timing process
+        println!("Done in {:.2?}", end.duration_since(start));
+
+        Ok(())
+    }
+}
+
+fn display_change(change: &UndoChange) {
+    let label = match change.action {
+        UndoAction::Deleted => paint_string!("{gray}", "deleted:  "),
+        UndoAction::Restored => paint_string!("{green}", "restored: "),
+    };
+
+    println!("{}{}", label, change.path);
+}
@@ -1,9 +1,13 @@
+use super::save::{
+    confirm_template_secrets, display_size_summary, get_replacements, get_size_limits,
+};
 use crate::cli::input;
 use crate::cli::input::check_flags;
 use crate::cli::input::command::Command;
 use crate::cli::input::namespaces::{get_repo_namespace_obj, NamespaceObject};
 use crate::core::repos::Repository;
-use crate::core::template::maker::make_template;
+use crate::core::template::inheritance::resolve_template;
+use crate::core::template::maker::{get_template_allowed_secrets, make_template};
 use crate::utils::date;
 use crate::utils::errors::invalid_input_error;
 use crate::write_help;
@@ -23,7 +27,13 @@ impl Update {
             return Ok(());
         }
 
-        let flags = vec!["--name", "--description"];
+        let flags = vec![
+            "--name",
+            "--description",
+            "--no-gitignore",
+            "--force",
+            "--allow-secrets",
+        ];
         check_flags(&command.flags, flags)?;
 
         if command.args.is_empty() {
@@ -55,13 +65,30 @@ impl Update {
         };
 
         let template = repo.get_template(&template_name)?;
-        let mut new_template =
-            make_template(template_name.clone(), directory, template.description)?;
+        let mut new_template = make_template(
+            template_name.clone(),
+            directory,
+            None,
+            !command.has_flag("--no-gitignore"),
+            &get_replacements(&command)?,
+            &get_size_limits(&command)?,
+        )?;
+
+        // The manifest description replaces the current one
+        if new_template.description.is_none() {
+            new_template.description = template.description;
+        }
 
         // Update template date fields
         new_template.created_at = template.created_at;
         new_template.updated_at = Some(date::get_date_now_string());
 
+        // Checks that the base templates exist, they are only applied at generation time
+        resolve_template(new_template.clone())?;
+
+        display_size_summary(&new_template);
+        let allowed_secrets = get_template_allowed_secrets(directory)?;
+        confirm_template_secrets(&command, &new_template, &allowed_secrets)?;
         repo.update_template_content(template_name.clone(), new_template)?;
 
         println!("Template \"{}\" was updated.", template_name);
@@ -0,0 +1,151 @@
+use crate::cli::input::check_flags;
+use crate::cli::input::command::Command;
+use crate::cli::input::namespaces::{get_repo_namespace_obj, NamespaceObject};
+use crate::core::http::str_is_url;
+use crate::core::repos::Repository;
+use crate::core::template::config::ConfigArg;
+use crate::core::template::engine::{get_engine_args_input, set_arg_default_value, TempEngineArg};
+use crate::core::template::inheritance::resolve_template;
+use crate::core::template::manifest::{make_manifest, read_manifest, write_manifest, GenManifest};
+use crate::core::template::snapshot::{get_snapshot, save_snapshot};
+use crate::core::template::upgrader::{upgrade_template, UpgradeAction, UpgradeChange};
+use crate::core::template::Template;
+use crate::utils::errors::invalid_input_error;
+use crate::{paint_string, paintln, write_help};
+use std::{io::Error, path::Path, time::Instant};
+
+pub struct Upgrade;
+
+impl Upgrade {
+    pub fn help() {
+        write_help!("../../help_files/upgrade.json");
+    }
+
+    pub fn run(command: Command) -> Result<(), Error> {
+        if command.has_help_flag() {
+            Self::help();
+            return Ok(());
+        }
+
+        check_flags(&command.flags, vec![])?;
+
+        let start = Instant::now(); // start timing process
+
+        let directory = if command.args.is_empty() {
+            Path::new(".")
+        } else {
+            Path::new(&command.args[0])
+        };
+
+        let manifest = read_manifest(directory)?;
+
+        if manifest.template.ends_with(".tpo") || str_is_url(&manifest.template) {
+            return Err(invalid_input_error(
+                "Only templates saved in a repository can be upgraded.",
+            ));
+        }
+
+        if manifest.template.contains(',') {
+            return Err(invalid_input_error(
+                "Generations of composed templates can not be upgraded.",
+            ));
+        }
+
+        let NamespaceObject {
+            repo_name,
+            template_name,
+        } = get_repo_namespace_obj(&manifest.template);
+        let repo = Repository::connect(repo_name)?;
+        let new_template = resolve_template(repo.get_template(&template_name)?)?;
+
+        if new_template.hash()? == manifest.template_hash {
+            println!("Template \"{}\" is already up to date.", template_name);
+            return Ok(());
+        }
+
+        let old_template = get_snapshot(&manifest.template_hash)?;
+        let temp_args = get_upgrade_args(&manifest, &old_template, &new_template)?;
+
+        paintln!("{gray}", "[upgrading files...]");
+        let (paths, changes) =
+            upgrade_template(&old_template, &new_template, &temp_args, directory)?;
+        changes.iter().for_each(display_change);
+        println!();
+
+        let new_manifest = make_manifest(
+            &manifest.template,
+            &new_template,
+            &temp_args,
+            directory,
+            &paths,
+        )?;
+        write_manifest(directory, &new_manifest)?;
+        save_snapshot(&new_template)?;
+
+        let conflicts = changes
+            .iter()
+            .filter(|change| change.action == UpgradeAction::Conflicted)
+            .count();
+
+        if conflicts > 0 {
+            println!(
+                "Template \"{}\" was upgraded with {} conflict(s), resolve the conflict markers.",
+                template_name, conflicts
+            );
+        } else {
+            println!("Template \"{}\" was upgraded.", template_name);
+        }
+
+        let end = Instant::now(); // stop timing process
+        println!("Done in {:.2?}", end.duration_since(start));
+
+        Ok(())
+    }
+}
+
+// Uses the answers recorded in the manifest, asking only for the
+// secret args and for the args added in the new template version.
+fn get_upgrade_args(
+    manifest: &GenManifest,
+    old_template: &Template,
+    new_template: &Template,
+) -> Result<Vec<TempEngineArg>, Error> {
+    let mut config_args: Vec<ConfigArg> = vec![];
+    let all_config_args = new_template
+        .args
+        .iter()
+        .chain(old_template.args.iter())
+        .flatten();
+
+    for config_arg in all_config_args {
+        if !config_args.iter().any(|arg| arg.key == config_arg.key) {
+            config_args.push(config_arg.clone());
+        }
+    }
+
+    let missing_args: Vec<ConfigArg> = config_args
+        .iter()
+        .filter(|config_arg| !manifest.args.iter().any(|arg| arg.key == config_arg.key))
+        .cloned()
+        .collect();
+
+    let mut temp_args = manifest.args.clone();
+    for arg in get_engine_args_input(&missing_args, false)? {
+        temp_args.push(set_arg_default_value(arg, &config_args)?);
+    }
+
+    Ok(temp_args)
+}
+
+fn display_change(change: &UpgradeChange) {
+    let label = match change.action {
+        UpgradeAction::Created => paint_string!("{green}", "created:  "),
+        UpgradeAction::Updated => paint_string!("{gray}", "updated:  "),
+        UpgradeAction::Merged => paint_string!("{gray}", "merged:   "),
+        UpgradeAction::Conflicted => paint_string!("{red}", "conflict: "),
+        UpgradeAction::Deleted => paint_string!("{gray}", "deleted:  "),
+        UpgradeAction::Kept => paint_string!("{yellow}", "kept:     "),
+    };
+
+    println!("{}{}", label, change.path);
+}
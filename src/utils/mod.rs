@@ -1,4 +1,5 @@
 pub mod errors;
 pub mod path;
 pub mod date;
+pub mod hash;
 pub mod string;
\ No newline at end of file
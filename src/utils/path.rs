@@ -1,33 +1,100 @@
-use super::errors::{invalid_input_error, std_error};
+use super::errors::{invalid_data_error, invalid_input_error, not_found_error};
 use crate::core::template::TempPath;
 use std::io::Error;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
+// Non UTF-8 characters are replaced, use valid_utf8_path to reject those paths
 pub fn pathbuf_to_string(path: PathBuf) -> String {
-    path.as_os_str().to_str().unwrap().to_string()
+    path.to_string_lossy().to_string()
 }
 
 pub fn str_to_pathbuf(path: &str) -> PathBuf {
     Path::new(path).to_path_buf()
 }
 
+// Absolute and relative paths are valid, with or without a trailing slash
 pub fn valid_directory_path(directory: &str) -> Result<(), Error> {
-    if directory.contains(r"\") {
-        return Err(invalid_input_error("Invalid directory path syntax."));
+    if directory.is_empty() {
+        return Err(invalid_input_error("The directory path cannot be empty."));
+    }
+
+    let path = Path::new(directory);
+
+    if !path.exists() {
+        return Err(not_found_error(&format!(
+            "The directory \"{}\" was not found.",
+            directory
+        )));
+    }
+
+    if !path.is_dir() {
+        return Err(invalid_input_error(&format!(
+            "\"{}\" is not a directory.",
+            directory
+        )));
     }
 
     Ok(())
 }
 
+pub fn valid_utf8_path(path: &Path) -> Result<(), Error> {
+    match path.to_str() {
+        Some(_) => Ok(()),
+        None => Err(invalid_data_error(&format!(
+            "The path \"{}\" has invalid UTF-8 characters.",
+            path.to_string_lossy()
+        ))),
+    }
+}
+
+// Both paths are normalized first, so "./dir", "dir/" and "dir" are the same
 pub fn remove_dir_prefix(path: TempPath, directory: &str) -> Result<TempPath, Error> {
-    let prefix = format!("{}/", directory);
-    let new_path = std_error(path.path.strip_prefix(prefix))?.to_path_buf();
+    let normalized_path = normalize_path(&path.path);
+    let new_path = match normalized_path.strip_prefix(normalize_path(Path::new(directory))) {
+        Ok(new_path) => new_path.to_path_buf(),
+        Err(_) => {
+            return Err(invalid_input_error(&format!(
+                "The path \"{}\" is not inside of \"{}\".",
+                path.path.to_string_lossy(),
+                directory
+            )))
+        }
+    };
+
     Ok(TempPath {
         path: new_path,
         path_type: path.path_type,
     })
 }
 
+// Lexical normalization, the path does not need to exist: "./dir", "dir/" and
+// "a/../dir" become "dir". The ".." that leave a relative path are kept, like
+// in "../../dir", and the ones right after the root are dropped.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
 pub fn format_path_namespace(path: PathBuf) -> PathBuf {
     Path::new(&pathbuf_to_string(path).replace(r"\", "/")).to_path_buf()
 }
+
+// Name used to compare template paths, so "src\\main.rs" and "src/main.rs" are the same
+pub fn get_path_name(path: &Path) -> String {
+    pathbuf_to_string(format_path_namespace(path.to_path_buf()))
+}